@@ -1,15 +1,37 @@
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{AdventError, Answer, Solver};
+
+pub struct __SOLVER__;
+
+impl Solver for __SOLVER__ {
+    fn year(&self) -> u32 {
+        __YEAR__
+    }
+
+    fn day(&self) -> u32 {
+        __DAY__
+    }
+
+    fn title(&self) -> &'static str {
+        "__TITLE__"
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// PART 1 :
-fn part_1(input: &str) -> String {
-    format!("{}", "")
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    Ok(Answer::from(""))
 }
 
 /// PART 2 :
-fn part_2(input: &str) -> String {
-    format!("{}", "")
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    Ok(Answer::from(""))
 }
 
 fn parse_input(input: &str) -> Vec<&str> {
@@ -24,12 +46,12 @@ mod tests {
     #[rstest]
     #[case("")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "");
+        assert_eq!(part_1(input), Ok(Answer::from("")));
     }
 
     #[rstest]
     #[case("")]
     fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "");
+        assert_eq!(part_2(input), Ok(Answer::from("")));
     }
 }
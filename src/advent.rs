@@ -1,43 +1,276 @@
 use std::fmt;
-use std::ops::RangeInclusive;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-mod advent_2021;
-mod advent_2022;
+#[macro_use]
+mod solver;
+mod answer;
+mod answers;
+mod bench;
+mod cancel;
+mod config;
+mod coverage;
+mod error;
+mod examples;
+mod fetch;
+mod fuzz;
+mod generate;
+mod history;
+mod input;
+mod isolate;
+mod output;
+mod part;
+mod pool;
+mod scaffold;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod timing;
+mod watch;
 
-const DAYS: RangeInclusive<u32> = 1..=25;
-const YEARS: RangeInclusive<u32> = 2015..=2022;
+#[cfg(feature = "y2021")]
+pub mod advent_2021;
+#[cfg(feature = "y2022")]
+pub mod advent_2022;
 
-pub fn select_all_challenges() -> Vec<Solution> {
-    YEARS
-        .flat_map(|year| select_challenges_from_year(year).into_iter())
-        .collect::<Vec<Solution>>()
+pub use answer::Answer;
+pub use answers::{record_answers, verify_answers, Answers, Check, Tally, Verdict};
+pub use bench::{bench_challenges, bench_report, Baseline, Bench, Stage, Stats};
+pub use cancel::{cancel_token, CancelToken};
+pub use config::{Config, CONFIG_FILE};
+pub use coverage::Coverage;
+pub use error::AdventError;
+pub use examples::{extract_examples, save_examples, Example};
+pub use fetch::{Fetched, Fetcher, BASE_URL};
+pub use fuzz::{fuzz, Mismatch, FUZZ_CASES, FUZZ_SIZE};
+pub use generate::{generate, Rng};
+pub use history::{git_commit, History, Run};
+pub use input::{input_dir_name, split_columns, Input};
+pub use output::{inputs_report, render, Format};
+pub use part::Part;
+pub use scaffold::scaffold;
+pub use solver::{Reference, Solver};
+pub use submit::{
+    now, submit_answer, submit_part, Attempt, Attempts, Outcome, Response, Submission, Submitter,
+};
+pub use timing::{parallel_summary, parse_duration, timing_report, Timing};
+pub use watch::{watch_report, Watcher, CLEAR_SCREEN};
+
+/// The solvers of every year, each compiled in only with its `yYYYY` feature.
+const REGISTRY: &[&[&dyn Solver]] = &[
+    #[cfg(feature = "y2021")]
+    advent_2021::SOLVERS,
+    #[cfg(feature = "y2022")]
+    advent_2022::SOLVERS,
+];
+
+/// Every registered solver, ordered by year and then by day.
+pub fn solvers() -> Vec<&'static dyn Solver> {
+    let mut solvers = REGISTRY
+        .iter()
+        .flat_map(|year| year.iter().copied())
+        .collect::<Vec<&dyn Solver>>();
+    solvers.sort_by_key(|solver| (solver.year(), solver.day()));
+    solvers
+}
+
+/// Settings shared by every challenge in a run.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub input: Input,
+    pub part: Part,
+    /// Also time the solver's separate parse step, which costs an extra parse.
+    pub time: bool,
+    /// How many challenges to solve at once, where 0 means one per core.
+    pub jobs: usize,
+    /// How long each challenge may take before it is reported as timed out
+    /// and its solver is cancelled.
+    pub timeout: Option<Duration>,
+    /// Called with each solution as soon as it and every solution before it
+    /// are done, so results can be shown while the rest are still running.
+    pub on_solution: Option<fn(&Solution)>,
+}
+
+impl Options {
+    /// The number of worker threads to use, resolving 0 to the core count.
+    pub fn jobs(&self) -> usize {
+        match self.jobs {
+            0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            jobs => jobs,
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            input: Input::default(),
+            part: Part::default(),
+            time: false,
+            jobs: 1,
+            timeout: None,
+            on_solution: None,
+        }
+    }
+}
+
+pub fn select_all_challenges(options: &Options) -> Vec<Solution> {
+    solve_challenges(solvers(), options)
+}
+
+pub fn select_challenges_from_year(year: u32, options: &Options) -> Vec<Solution> {
+    solve_challenges(
+        solvers()
+            .into_iter()
+            .filter(|solver| solver.year() == year)
+            .collect::<Vec<&dyn Solver>>(),
+        options,
+    )
 }
 
-pub fn select_challenges_from_year(year: u32) -> Vec<Solution> {
-    DAYS.filter_map(|day| select_challenge(year, day))
-        .collect::<Vec<Solution>>()
+pub fn select_challenges_with_day(day: u32, options: &Options) -> Vec<Solution> {
+    solve_challenges(
+        solvers()
+            .into_iter()
+            .filter(|solver| solver.day() == day)
+            .collect::<Vec<&dyn Solver>>(),
+        options,
+    )
 }
 
-pub fn select_challenges_with_day(day: u32) -> Vec<Solution> {
-    YEARS
-        .filter_map(|year| select_challenge(year, day))
-        .collect::<Vec<Solution>>()
+pub fn select_challenge(year: u32, day: u32, options: &Options) -> Option<Solution> {
+    solve_challenges(
+        solvers()
+            .into_iter()
+            .filter(|solver| solver.year() == year && solver.day() == day)
+            .collect::<Vec<&dyn Solver>>(),
+        options,
+    )
+    .pop()
+}
+
+/// Solves the challenges of the given year and day, where 0 selects every
+/// year or day, on each of their inputs in `dir`. Every solution is paired
+/// with the name of the input it solved.
+pub fn select_challenges_on_all_inputs(
+    year: u32,
+    day: u32,
+    dir: &Path,
+    options: &Options,
+) -> Vec<(String, Solution)> {
+    let runs = solvers()
+        .into_iter()
+        .filter(|solver| (year == 0 || solver.year() == year) && (day == 0 || solver.day() == day))
+        .flat_map(|solver| {
+            input::inputs_of(dir, solver.year(), solver.day())
+                .into_iter()
+                .map(move |(name, input)| (solver, name, input))
+        })
+        .collect::<Vec<(&dyn Solver, String, Input)>>();
+    pool::run_ordered(
+        &runs,
+        options.jobs(),
+        |(solver, name, input)| {
+            let options = Options {
+                input: input.clone(),
+                on_solution: None,
+                ..options.clone()
+            };
+            (name.clone(), run(*solver, &options))
+        },
+        |_| (),
+    )
 }
 
-pub fn select_challenge(year: u32, day: u32) -> Option<Solution> {
-    let solve = match year {
-        2021 => advent_2021::select_day(day),
-        2022 => advent_2022::select_day(day),
-        _ => None,
+/// Solves the challenges on `options.jobs()` threads, keeping their order.
+fn solve_challenges(solvers: Vec<&'static dyn Solver>, options: &Options) -> Vec<Solution> {
+    pool::run_ordered(
+        &solvers,
+        options.jobs(),
+        |solver| run(*solver, options),
+        |solution| {
+            if let Some(on_solution) = options.on_solution {
+                on_solution(solution);
+            }
+        },
+    )
+}
+
+fn run(solver: &'static dyn Solver, options: &Options) -> Solution {
+    let input = options
+        .input
+        .read(solver.year(), solver.day())
+        .map(Arc::<str>::from);
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    let token = CancelToken::default();
+    let mut timing = Timing::default();
+    if let (true, Ok(input)) = (options.time, &input) {
+        let input = Arc::clone(input);
+        if let Some((Ok(Some(_)), duration)) = run_step(deadline, &token, move || {
+            isolate::isolate(|| solver.parse(&input))
+        }) {
+            timing.parse = Some(duration);
+        }
+    }
+    let solve = |part: u8| {
+        let input = input.clone();
+        let start = Instant::now();
+        run_step(deadline, &token, move || {
+            solve_part(&input, |input| match part {
+                1 => solver.part_1(input),
+                _ => solver.part_2(input),
+            })
+        })
+        .unwrap_or_else(|| {
+            let timeout = options.timeout.unwrap_or_default();
+            (Err(AdventError::Timeout(timeout)), start.elapsed())
+        })
     };
-    match solve {
-        Some((part1, part2)) => Some(Solution {
-            year,
-            day,
-            part1,
-            part2,
-        }),
-        None => None,
+    let part1 = options.part.includes_part_1().then(|| {
+        let (answer, duration) = solve(1);
+        timing.part1 = Some(duration);
+        answer
+    });
+    let part2 = options.part.includes_part_2().then(|| {
+        let (answer, duration) = solve(2);
+        timing.part2 = Some(duration);
+        answer
+    });
+    Solution {
+        year: solver.year(),
+        day: solver.day(),
+        title: solver.title(),
+        part1,
+        part2,
+        timing,
+    }
+}
+
+/// Runs and times one step of a challenge. When the challenge has a
+/// deadline, the step runs on a thread of its own and is `None` if it
+/// doesn't finish in time.
+fn run_step<R, F>(deadline: Option<Instant>, token: &CancelToken, step: F) -> Option<(R, Duration)>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    match deadline {
+        Some(deadline) => cancel::with_deadline(deadline, token, move || timing::time(step)),
+        None => Some(timing::time(step)),
+    }
+}
+
+/// Solves a part behind a panic boundary, unless its input couldn't be read.
+fn solve_part<F>(input: &Result<Arc<str>, AdventError>, solve: F) -> Result<Answer, AdventError>
+where
+    F: FnOnce(&str) -> Result<Answer, AdventError>,
+{
+    match input {
+        Ok(input) => isolate::isolate(|| solve(input)).and_then(|answer| answer),
+        Err(err) => Err(err.clone()),
     }
 }
 
@@ -45,16 +278,191 @@ pub fn select_challenge(year: u32, day: u32) -> Option<Solution> {
 pub struct Solution {
     year: u32,
     day: u32,
-    part1: String,
-    part2: String,
+    title: &'static str,
+    /// `None` when the part was not selected for this run.
+    part1: Option<Result<Answer, AdventError>>,
+    part2: Option<Result<Answer, AdventError>>,
+    timing: Timing,
+}
+
+impl Solution {
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+
+    pub fn timing(&self) -> &Timing {
+        &self.timing
+    }
+
+    /// The errors of any parts that failed, in part order. An error shared by
+    /// both parts, such as a missing input, is only reported once.
+    pub fn errors(&self) -> Vec<&AdventError> {
+        let mut errors = [&self.part1, &self.part2]
+            .into_iter()
+            .filter_map(|part| part.as_ref()?.as_ref().err())
+            .collect::<Vec<&AdventError>>();
+        errors.dedup();
+        errors
+    }
+
+    /// The answer of part 1 or 2, or `None` if that part wasn't run.
+    pub fn answer(&self, part: u8) -> Option<&Result<Answer, AdventError>> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.errors().is_empty()
+    }
 }
 
 impl fmt::Display for Solution {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Solution {{year = {}}}, {{day = {}}} => ({}, {})",
-            self.year, self.day, self.part1, self.part2
+            "Solution {{year = {}}}, {{day = {}}}, {{title = {}}} => ({}, {})",
+            self.year,
+            self.day,
+            self.title,
+            fmt_part(&self.part1),
+            fmt_part(&self.part2)
         )
     }
 }
+
+fn fmt_part(part: &Option<Result<Answer, AdventError>>) -> String {
+    match part {
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(AdventError::Timeout(_))) => String::from("TIMEOUT"),
+        Some(Err(_)) => String::from("FAILED"),
+        None => String::from("-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    struct Panicky;
+
+    impl Solver for Panicky {
+        fn year(&self) -> u32 {
+            2022
+        }
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Panicky"
+        }
+
+        fn parse(&self, _input: &str) -> Option<Result<(), AdventError>> {
+            panic!("parse panicked")
+        }
+
+        fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+            Ok(Answer::from(input.lines().collect::<Vec<&str>>()[1000]))
+        }
+
+        fn part_2(&self, _input: &str) -> Result<Answer, AdventError> {
+            Ok(Answer::from(7))
+        }
+    }
+
+    struct Endless;
+
+    impl Solver for Endless {
+        fn year(&self) -> u32 {
+            2022
+        }
+
+        fn day(&self) -> u32 {
+            2
+        }
+
+        fn title(&self) -> &'static str {
+            "Endless"
+        }
+
+        fn part_1(&self, _input: &str) -> Result<Answer, AdventError> {
+            loop {
+                cancel_token().check()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+
+        fn part_2(&self, _input: &str) -> Result<Answer, AdventError> {
+            Ok(Answer::from(7))
+        }
+    }
+
+    fn manifest() -> Input {
+        Input::File(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+    }
+
+    #[rstest]
+    #[case(Part::One, Err(AdventError::Timeout(Duration::from_millis(50))))]
+    #[case(Part::Two, Ok(Answer::from(7)))]
+    fn test_timeout(#[case] part: Part, #[case] expected: Result<Answer, AdventError>) {
+        let options = Options {
+            input: manifest(),
+            part,
+            timeout: Some(Duration::from_millis(50)),
+            ..Options::default()
+        };
+        let solution = run(&Endless, &options);
+        let answer = match part {
+            Part::One => solution.answer(1),
+            _ => solution.answer(2),
+        };
+        assert_eq!(answer, Some(&expected));
+    }
+
+    #[rstest]
+    fn test_timeout_display() {
+        let options = Options {
+            input: manifest(),
+            timeout: Some(Duration::from_millis(20)),
+            ..Options::default()
+        };
+        assert_eq!(
+            run(&Endless, &options).to_string(),
+            "Solution {year = 2022}, {day = 2}, {title = Endless} => (TIMEOUT, TIMEOUT)"
+        );
+    }
+
+    #[rstest]
+    fn test_panic_isolation() {
+        let options = Options {
+            input: manifest(),
+            time: true,
+            jobs: 2,
+            ..Options::default()
+        };
+        let solutions = solve_challenges(vec![&Panicky, &Panicky], &options);
+        assert_eq!(solutions.len(), 2);
+        for solution in solutions {
+            assert!(matches!(
+                solution.answer(1),
+                Some(Err(AdventError::Panic(message))) if message.starts_with("index out of bounds")
+            ));
+            assert_eq!(solution.answer(2), Some(&Ok(Answer::from(7))));
+            assert_eq!(solution.timing.parse, None);
+        }
+    }
+}
@@ -1,5 +1,42 @@
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{AdventError, Answer, Reference, Rng, Solver};
+
+pub struct SonarSweep;
+
+impl Solver for SonarSweep {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Sonar Sweep"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// As the submarine drops below the surface of the ocean, it automatically
@@ -13,8 +50,8 @@ pub fn solve(input: &str) -> (String, String) {
 ///
 /// PART 1 : Count the number of times a depth measurement increases from the
 /// previous measurement.
-fn part_1(input: &str) -> String {
-    let depths = parse_input(input);
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let depths = parse_input(input)?;
     let depth_increases = (1..depths.len()).fold(0, |count, idx| {
         if depths[idx] > depths[idx - 1] {
             count + 1
@@ -22,7 +59,7 @@ fn part_1(input: &str) -> String {
             count
         }
     });
-    format!("{}", depth_increases)
+    Ok(Answer::from(depth_increases))
 }
 
 /// Considering every single measurement isn't as useful as you expected: there's
@@ -30,8 +67,8 @@ fn part_1(input: &str) -> String {
 /// sliding window.
 /// PART 2 : Consider sums of a three-measurement sliding window. How many
 /// sums are larger than the previous sum?
-fn part_2(input: &str) -> String {
-    let depths = parse_input(input);
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let depths = parse_input(input)?;
     let depth_increases = (3..depths.len()).fold(0, |count, n| {
         if depths[n] > depths[n - 3] {
             count + 1
@@ -39,14 +76,57 @@ fn part_2(input: &str) -> String {
             count
         }
     });
-    format!("{}", depth_increases)
+    Ok(Answer::from(depth_increases))
+}
+
+fn parse_input(input: &str) -> Result<Vec<u32>, AdventError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.parse::<u32>()
+                .map_err(|_| AdventError::parse(idx, 0, line, "parse error on depth"))
+        })
+        .collect::<Result<Vec<u32>, AdventError>>()
+}
+
+/// Generates `size` depth measurements, at least one, that drift deeper with
+/// some noise.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    let mut depth = rng.range(100..=200);
+    (0..size.max(1))
+        .map(|_| {
+            depth = (depth + rng.range(0..=40)).saturating_sub(15).max(1);
+            depth.to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Counts the increases by comparing every depth with the one before it.
+fn reference_1(input: &str) -> Option<Answer> {
+    let depths = reference_input(input)?;
+    Some(Answer::from(
+        depths.windows(2).filter(|pair| pair[1] > pair[0]).count(),
+    ))
+}
+
+/// Sums every window of three depths and counts the increases between them.
+fn reference_2(input: &str) -> Option<Answer> {
+    let sums = reference_input(input)?
+        .windows(3)
+        .map(|window| window.iter().sum::<u64>())
+        .collect::<Vec<u64>>();
+    Some(Answer::from(
+        sums.windows(2).filter(|pair| pair[1] > pair[0]).count(),
+    ))
 }
 
-fn parse_input(input: &str) -> Vec<u32> {
+fn reference_input(input: &str) -> Option<Vec<u64>> {
     input
         .lines()
-        .map(|line| line.parse::<u32>().expect("parse error on depth"))
-        .collect::<Vec<u32>>()
+        .map(|line| line.parse::<u32>().ok().map(u64::from))
+        .collect::<Option<Vec<u64>>>()
 }
 
 #[cfg(test)]
@@ -57,12 +137,12 @@ mod tests {
     #[rstest]
     #[case("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "7");
+        assert_eq!(part_1(input), Ok(Answer::from(7)));
     }
 
     #[rstest]
     #[case("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")]
     fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "5");
+        assert_eq!(part_2(input), Ok(Answer::from(5)));
     }
 }
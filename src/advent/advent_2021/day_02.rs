@@ -1,7 +1,44 @@
 use std::str::FromStr;
 
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{split_columns, AdventError, Answer, Reference, Rng, Solver};
+
+pub struct Dive;
+
+impl Solver for Dive {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Dive!"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// Now, you need to figure out how to pilot this thing. It seems like the
@@ -15,16 +52,18 @@ pub fn solve(input: &str) -> (String, String) {
 /// PART 1 : Calculate the horizontal position and depth you would have after
 /// following the planned course. What do you get if you multiply your final
 /// horizontal position by your final depth?
-fn part_1(input: &str) -> String {
-    let (pos, depth) =
-        parse_input(input)
-            .iter()
-            .fold((0, 0), |(pos, depth), command| match command.op {
-                SubOp::Forward => (pos + command.dist, depth),
-                SubOp::Down => (pos, depth + command.dist),
-                SubOp::Up => (pos, depth - command.dist),
-            });
-    format!("{}", pos * depth)
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let (pos, depth) = parse_input(input)?
+        .iter()
+        .fold((0i64, 0i64), |(pos, depth), command| {
+            let dist = i64::from(command.dist);
+            match command.op {
+                SubOp::Forward => (pos + dist, depth),
+                SubOp::Down => (pos, depth + dist),
+                SubOp::Up => (pos, depth - dist),
+            }
+        });
+    Ok(Answer::from(pos * depth))
 }
 
 /// In addition to horizontal position and depth, you'll also need to track
@@ -40,23 +79,27 @@ fn part_1(input: &str) -> String {
 /// horizontal position and depth you would have after following the planned
 /// course. What do you get if you multiply your final horizontal position by
 /// your final depth?
-fn part_2(input: &str) -> String {
+fn part_2(input: &str) -> Result<Answer, AdventError> {
     let (pos, depth, _) =
-        parse_input(input)
+        parse_input(input)?
             .iter()
-            .fold((0, 0, 0), |(pos, depth, aim), command| match command.op {
-                SubOp::Forward => (pos + command.dist, depth + aim * command.dist, aim),
-                SubOp::Down => (pos, depth, aim + command.dist),
-                SubOp::Up => (pos, depth, aim - command.dist),
+            .fold((0i64, 0i64, 0i64), |(pos, depth, aim), command| {
+                let dist = i64::from(command.dist);
+                match command.op {
+                    SubOp::Forward => (pos + dist, depth + aim * dist, aim),
+                    SubOp::Down => (pos, depth, aim + dist),
+                    SubOp::Up => (pos, depth, aim - dist),
+                }
             });
-    format!("{}", pos * depth)
+    Ok(Answer::from(pos * depth))
 }
 
-fn parse_input(input: &str) -> Vec<Command> {
+fn parse_input(input: &str) -> Result<Vec<Command>, AdventError> {
     input
         .lines()
-        .map(|line| Command::from(line))
-        .collect::<Vec<Command>>()
+        .enumerate()
+        .map(|(idx, line)| Command::parse(idx, line))
+        .collect::<Result<Vec<Command>, AdventError>>()
 }
 
 #[derive(Debug)]
@@ -85,21 +128,99 @@ struct Command {
     pub dist: u32,
 }
 
-impl From<&str> for Command {
-    fn from(line: &str) -> Command {
-        let parts = line.split(" ").collect::<Vec<&str>>();
-        assert_eq!(
-            parts.len(),
-            2,
-            "commands should include two space-delimited parts"
-        );
-        Command {
-            op: parts[0].parse::<SubOp>().expect("parse error on SubOp"),
-            dist: parts[1].parse::<u32>().expect("parse error on X"),
+impl Command {
+    fn parse(idx: usize, line: &str) -> Result<Command, AdventError> {
+        let parts = split_columns(line, ' ');
+        if parts.len() != 2 {
+            return Err(AdventError::parse(
+                idx,
+                0,
+                line,
+                "commands should include two space-delimited parts",
+            ));
         }
+        let ((op_col, op), (dist_col, dist)) = (parts[0], parts[1]);
+        Ok(Command {
+            op: op
+                .parse::<SubOp>()
+                .map_err(|reason| AdventError::parse(idx, op_col, op, reason))?,
+            dist: dist
+                .parse::<u32>()
+                .map_err(|_| AdventError::parse(idx, dist_col, dist, "parse error on X"))?,
+        })
     }
 }
 
+/// Generates `size` commands, at least one, never going up further than the
+/// aim allows.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    let mut aim = 0;
+    (0..size.max(1))
+        .map(|_| {
+            let dist = rng.range(1..=9);
+            let op = match rng.range(0..=2) {
+                0 => "forward",
+                1 if aim >= dist => {
+                    aim -= dist;
+                    "up"
+                }
+                _ => {
+                    aim += dist;
+                    "down"
+                }
+            };
+            format!("{} {}", op, dist)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Steers one command at a time, where up and down move the submarine.
+fn reference_1(input: &str) -> Option<Answer> {
+    let (mut pos, mut depth) = (0, 0);
+    for (op, dist) in reference_input(input)? {
+        match op {
+            "forward" => pos += dist,
+            "down" => depth += dist,
+            _ => depth -= dist,
+        }
+        if depth < 0 {
+            return None;
+        }
+    }
+    Some(Answer::from(pos * depth))
+}
+
+/// Steers one command at a time, where up and down only change the aim.
+fn reference_2(input: &str) -> Option<Answer> {
+    let (mut pos, mut depth, mut aim) = (0, 0, 0);
+    for (op, dist) in reference_input(input)? {
+        match op {
+            "forward" => {
+                pos += dist;
+                depth += aim * dist;
+            }
+            "down" => aim += dist,
+            _ => aim -= dist,
+        }
+        if depth < 0 || aim < 0 {
+            return None;
+        }
+    }
+    Some(Answer::from(pos * depth))
+}
+
+fn reference_input(input: &str) -> Option<Vec<(&str, i64)>> {
+    input
+        .lines()
+        .map(|line| {
+            let (op, dist) = line.split_once(' ')?;
+            let dist = i64::from(dist.parse::<u32>().ok()?);
+            matches!(op, "forward" | "down" | "up").then_some((op, dist))
+        })
+        .collect::<Option<Vec<(&str, i64)>>>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,12 +229,29 @@ mod tests {
     #[rstest]
     #[case("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "150");
+        assert_eq!(part_1(input), Ok(Answer::from(150)));
     }
 
     #[rstest]
     #[case("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2")]
     fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "900");
+        assert_eq!(part_2(input), Ok(Answer::from(900)));
+    }
+
+    #[rstest]
+    #[case("forward 5\nsideways 5", 2, 1, "sideways")]
+    #[case("forward 5\ndown five", 2, 6, "five")]
+    #[case("forward 5\ndown", 2, 1, "down")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        assert!(matches!(
+            part_1(input),
+            Err(AdventError::Parse { line: l, column: c, text: t, .. })
+                if l == line && c == column && t == text
+        ));
     }
 }
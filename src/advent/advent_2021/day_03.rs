@@ -1,5 +1,42 @@
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{AdventError, Answer, Reference, Rng, Solver};
+
+pub struct BinaryDiagnostic;
+
+impl Solver for BinaryDiagnostic {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Binary Diagnostic"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// The diagnostic report (your puzzle input) consists of a list of binary
@@ -16,9 +53,12 @@ pub fn solve(input: &str) -> (String, String) {
 /// PART 1 : Use the binary numbers in your diagnostic report to calculate the
 /// gamma rate and epsilon rate, then multiply them together. What is the power
 /// consumption of the submarine?
-fn part_1(input: &str) -> String {
-    let diagnostics = parse_input(input);
-    let m = diagnostics[0].len();
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let diagnostics = parse_input(input)?;
+    let m = diagnostics
+        .first()
+        .ok_or_else(|| AdventError::no_solution("diagnostic report is empty"))?
+        .len();
     let (gamma_rate, epsilon_rate) = (0..m)
         .map(|k| {
             let (num_zeroes, num_ones) = diagnostics
@@ -37,13 +77,13 @@ fn part_1(input: &str) -> String {
                 (0, 1)
             }
         })
-        .fold((0, 0), |(gamma_rate, epsilon_rate), bit_pair| {
+        .fold((0u64, 0u64), |(gamma_rate, epsilon_rate), bit_pair| {
             (
                 (gamma_rate << 1) + bit_pair.0,
                 (epsilon_rate << 1) + bit_pair.1,
             )
         });
-    format!("{}", gamma_rate * epsilon_rate)
+    Ok(Answer::from(gamma_rate * epsilon_rate))
 }
 
 /// Next, you should verify the life support rating, which can be determined by
@@ -72,17 +112,25 @@ fn part_1(input: &str) -> String {
 /// PART 2 : Use the binary numbers in your diagnostic report to calculate the
 /// oxygen generator rating and CO2 scrubber rating, then multiply them together.
 /// What is the life support rating of the submarine?
-fn part_2(input: &str) -> String {
-    let diagnostics = parse_input(input);
-    let m = diagnostics[0].len();
-    let mut prefixes = vec![0u32; (1 << (m + 1)) - 1];
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let diagnostics = parse_input(input)?;
+    let m = diagnostics
+        .first()
+        .ok_or_else(|| AdventError::no_solution("diagnostic report is empty"))?
+        .len();
+    if m > MAX_PREFIX_BITS {
+        return Err(AdventError::NoSolution(format!(
+            "diagnostics of more than {} bits would need too large a prefix table",
+            MAX_PREFIX_BITS
+        )));
+    }
+    let mut prefixes = vec![0u32; 1 << (m + 1)];
     (0..m)
-        .map(|k| {
+        .flat_map(|k| {
             diagnostics
                 .iter()
                 .map(move |diagnostic| (1 << (k + 1)) | diagnostic.prefix(k))
         })
-        .flatten()
         .for_each(|prefix| prefixes[prefix] += 1);
     let (oxygen_rate, co2_rate) = (0..m).fold((0, 0), |(mut oxygen_rate, mut co2_rate), k| {
         let oxygen_prefix = ((1 << k) | oxygen_rate) << 1;
@@ -101,14 +149,30 @@ fn part_2(input: &str) -> String {
         }
         (oxygen_rate, co2_rate)
     });
-    format!("{}", oxygen_rate * co2_rate)
+    Ok(Answer::from(oxygen_rate as u64 * co2_rate as u64))
 }
 
-fn parse_input(input: &str) -> Vec<Diagnostic> {
-    input
+/// The widest diagnostics `part_2` counts the prefixes of, which keeps its
+/// table of every prefix at 8 MiB.
+const MAX_PREFIX_BITS: usize = 20;
+
+fn parse_input(input: &str) -> Result<Vec<Diagnostic>, AdventError> {
+    let diagnostics = input
         .lines()
-        .map(|line| Diagnostic::from(line))
-        .collect::<Vec<Diagnostic>>()
+        .enumerate()
+        .map(|(idx, line)| Diagnostic::parse(idx, line))
+        .collect::<Result<Vec<Diagnostic>, AdventError>>()?;
+    for (idx, line) in input.lines().enumerate() {
+        if diagnostics[idx].len() != diagnostics[0].len() {
+            return Err(AdventError::parse(
+                idx,
+                0,
+                line,
+                "diagnostics should all have the same number of bits",
+            ));
+        }
+    }
+    Ok(diagnostics)
 }
 
 #[derive(Debug)]
@@ -118,6 +182,36 @@ struct Diagnostic {
 }
 
 impl Diagnostic {
+    fn parse(idx: usize, line: &str) -> Result<Diagnostic, AdventError> {
+        if line.is_empty() || line.len() > 31 {
+            return Err(AdventError::parse(
+                idx,
+                0,
+                line,
+                "diagnostics should have between 1 and 31 bits",
+            ));
+        }
+        let mut length = 0;
+        let mut report = 0;
+        for (col, ch) in line.char_indices() {
+            report <<= 1;
+            match ch {
+                '0' => (),
+                '1' => report += 1,
+                _ => {
+                    return Err(AdventError::parse(
+                        idx,
+                        col,
+                        &line[col..(col + ch.len_utf8())],
+                        "diagnostics should only contain '0' and '1'",
+                    ))
+                }
+            }
+            length += 1;
+        }
+        Ok(Diagnostic { length, report })
+    }
+
     fn digit_at(&self, k: usize) -> u32 {
         self.report & (1 << (self.length - 1 - k))
     }
@@ -131,19 +225,65 @@ impl Diagnostic {
     }
 }
 
-impl From<&str> for Diagnostic {
-    fn from(line: &str) -> Diagnostic {
-        let mut length = 0;
-        let mut report = 0;
-        line.chars().for_each(|ch| {
-            report <<= 1;
-            if ch == '1' {
-                report += 1;
-            }
-            length += 1;
-        });
-        Diagnostic { length, report }
+/// Generates `size` twelve bit diagnostics, at least one.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{:012b}", rng.range(0..=0xfff)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Counts the ones of every column to find the most and least common bits.
+fn reference_1(input: &str) -> Option<Answer> {
+    let diagnostics = reference_input(input)?;
+    let (mut gamma_rate, mut epsilon_rate) = (0u64, 0u64);
+    for k in 0..diagnostics[0].len() {
+        let ones = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.as_bytes()[k] == b'1')
+            .count();
+        let most_common = u64::from(2 * ones >= diagnostics.len());
+        gamma_rate = (gamma_rate << 1) | most_common;
+        epsilon_rate = (epsilon_rate << 1) | (1 - most_common);
     }
+    Some(Answer::from(gamma_rate * epsilon_rate))
+}
+
+/// Filters the diagnostics literally, one bit at a time, until one is left.
+fn reference_2(input: &str) -> Option<Answer> {
+    let diagnostics = reference_input(input)?;
+    let rating = |keep_most_common: bool| {
+        let mut left = diagnostics.clone();
+        for k in 0..diagnostics[0].len() {
+            let ones = left
+                .iter()
+                .filter(|diagnostic| diagnostic.as_bytes()[k] == b'1')
+                .count();
+            let most_common = if 2 * ones >= left.len() { b'1' } else { b'0' };
+            let kept = left
+                .iter()
+                .filter(|diagnostic| (diagnostic.as_bytes()[k] == most_common) == keep_most_common)
+                .copied()
+                .collect::<Vec<&str>>();
+            if !kept.is_empty() {
+                left = kept;
+            }
+        }
+        u64::from_str_radix(left[0], 2).ok()
+    };
+    Some(Answer::from(rating(true)? * rating(false)?))
+}
+
+fn reference_input(input: &str) -> Option<Vec<&str>> {
+    let diagnostics = input.lines().collect::<Vec<&str>>();
+    let len = diagnostics.first()?.len();
+    diagnostics
+        .iter()
+        .all(|diagnostic| {
+            diagnostic.len() == len && diagnostic.chars().all(|ch| ch == '0' || ch == '1')
+        })
+        .then_some(diagnostics)
+        .filter(|_| (1..=31).contains(&len))
 }
 
 #[cfg(test)]
@@ -152,14 +292,33 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case("00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010")]
-    fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "198");
+    #[case(
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010",
+        198
+    )]
+    #[case("1010101010101010101010", 3909372812402)]
+    fn test_part_1(#[case] input: &str, #[case] expected: u64) {
+        assert_eq!(part_1(input), Ok(Answer::from(expected)));
+    }
+
+    #[rstest]
+    #[case(
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010",
+        230
+    )]
+    #[case("111\n011\n001", 21)]
+    #[case("1", 1)]
+    fn test_part_2(#[case] input: &str, #[case] expected: u32) {
+        assert_eq!(part_2(input), Ok(Answer::from(expected)));
     }
 
     #[rstest]
-    #[case("00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010")]
-    fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "230");
+    fn test_part_2_too_wide() {
+        assert_eq!(
+            part_2("1010101010101010101010"),
+            Err(AdventError::no_solution(
+                "diagnostics of more than 20 bits would need too large a prefix table"
+            ))
+        );
     }
 }
@@ -1,5 +1,46 @@
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{cancel_token, split_columns, AdventError, Answer, Reference, Rng, Solver};
+
+pub struct GiantSquid;
+
+impl Solver for GiantSquid {
+    fn year(&self) -> u32 {
+        2021
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Giant Squid"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(
+            parse_input_queries(input)
+                .and(parse_input_boards(input))
+                .map(|_| ()),
+        )
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// Bingo is played on a set of boards each consisting of a 5x5 grid of numbers.
@@ -17,26 +58,19 @@ pub fn solve(input: &str) -> (String, String) {
 ///
 /// PART 1 : To guarantee victory against the giant squid, figure out which
 /// board will win first. What will your final score be if you choose that board?
-fn part_1(input: &str) -> String {
-    let queries = parse_input_queries(input);
-    let mut boards = parse_input_boards(input);
-    let final_score = queries.iter().fold(0, |final_score, q| {
-        if final_score > 0 {
-            return final_score;
-        }
-        boards.iter_mut().fold(0, |score, board| {
-            if score > 0 {
-                return score;
-            }
-            board.update(*q);
-            if board.has_won() {
-                q * board.sum()
-            } else {
-                0
-            }
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let queries = parse_input_queries(input)?;
+    let mut boards = parse_input_boards(input)?;
+    let final_score = queries
+        .iter()
+        .find_map(|q| {
+            boards.iter_mut().find_map(|board| {
+                board.update(*q);
+                board.has_won().then(|| q * board.sum())
+            })
         })
-    });
-    format!("{}", final_score)
+        .ok_or_else(|| AdventError::no_solution("no board ever wins"))?;
+    Ok(Answer::from(final_score))
 }
 
 /// On the other hand, it might be wise to try a different strategy: let the
@@ -47,75 +81,97 @@ fn part_1(input: &str) -> String {
 ///
 /// PART 2 : Figure out which board will win last. Once it wins, what would
 /// its final score be?
-fn part_2(input: &str) -> String {
-    let queries = parse_input_queries(input);
-    let mut boards = parse_input_boards(input);
-    let final_score = queries.iter().fold(0, |final_score, q| {
-        boards.iter_mut().fold(final_score, |score, board| {
-            if board.has_won() {
-                return score;
-            }
-            board.update(*q);
-            if board.has_won() {
-                q * board.sum()
-            } else {
-                score
-            }
-        })
-    });
-    format!("{}", final_score)
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let queries = parse_input_queries(input)?;
+    let mut boards = parse_input_boards(input)?;
+    let cancel = cancel_token();
+    let final_score = queries
+        .iter()
+        .try_fold(None, |final_score, q| {
+            cancel.check()?;
+            Ok(boards.iter_mut().fold(final_score, |score, board| {
+                if board.has_won() {
+                    return score;
+                }
+                board.update(*q);
+                if board.has_won() {
+                    Some(q * board.sum())
+                } else {
+                    score
+                }
+            }))
+        })?
+        .ok_or_else(|| AdventError::no_solution("no board ever wins"))?;
+    Ok(Answer::from(final_score))
 }
 
-fn parse_input_queries(input: &str) -> Vec<u32> {
-    input
+fn parse_input_queries(input: &str) -> Result<Vec<u32>, AdventError> {
+    let line = input
         .lines()
-        .nth(0)
-        .expect("no initial query provided")
-        .split(",")
-        .map(|part| part.parse::<u32>().expect("parse error on query"))
-        .collect::<Vec<u32>>()
+        .next()
+        .ok_or_else(|| AdventError::no_solution("no initial query provided"))?;
+    split_columns(line, ',')
+        .into_iter()
+        .map(|(col, part)| {
+            part.parse::<u32>()
+                .map_err(|_| AdventError::parse(0, col, part, "parse error on query"))
+        })
+        .collect::<Result<Vec<u32>, AdventError>>()
 }
 
-fn parse_input_boards(input: &str) -> Vec<BingoBoard> {
-    let lines = input.split("\n").collect::<Vec<&str>>();
-    (7..=lines.len())
+fn parse_input_boards(input: &str) -> Result<Vec<BingoBoard>, AdventError> {
+    let lines = input.split('\n').collect::<Vec<&str>>();
+    let boards = (7..=lines.len())
         .step_by(6)
-        .map(|n| BingoBoard::from(&lines[(n - 5)..n]))
-        .collect::<Vec<BingoBoard>>()
+        .map(|n| BingoBoard::parse(n - 5, &lines[(n - 5)..n]))
+        .collect::<Result<Vec<BingoBoard>, AdventError>>()?;
+    if boards.is_empty() {
+        return Err(AdventError::no_solution("no bingo boards provided"));
+    }
+    Ok(boards)
 }
 
+/// A 5x5 bingo board. Row and column 0 hold how many numbers of each row
+/// and column are still unmarked, and `board[0][0]` the sum of the
+/// unmarked numbers.
 #[derive(Debug)]
-struct BingoBoard {
+pub struct BingoBoard {
     pub board: [[u32; 6]; 6],
 }
 
-impl From<&[&str]> for BingoBoard {
-    fn from(lines: &[&str]) -> BingoBoard {
+impl BingoBoard {
+    /// Parses the five rows of a board, the first of which is the
+    /// `first_idx`-th line of the input.
+    pub fn parse(first_idx: usize, lines: &[&str]) -> Result<BingoBoard, AdventError> {
         let mut board = [[0u32; 6]; 6];
-        lines
-            .iter()
-            .zip(1..=5)
-            .map(|(line, row)| {
-                line.split(" ")
-                    .filter(|part| !part.is_empty())
-                    .zip(1..=5)
-                    .map(move |(part, col)| {
-                        (row, col, part.parse::<u32>().expect("parse error on board"))
-                    })
-            })
-            .flatten()
-            .for_each(|(row, col, num)| {
+        for (idx, (line, row)) in (first_idx..).zip(lines.iter().zip(1..=5)) {
+            let parts = split_columns(line, ' ')
+                .into_iter()
+                .filter(|(_, part)| !part.is_empty())
+                .collect::<Vec<(usize, &str)>>();
+            if parts.len() != 5 {
+                return Err(AdventError::parse(
+                    idx,
+                    0,
+                    line,
+                    "board rows should include five numbers",
+                ));
+            }
+            for ((offset, part), col) in parts.into_iter().zip(1..=5) {
+                let num = part
+                    .parse::<u32>()
+                    .map_err(|_| AdventError::parse(idx, offset, part, "parse error on board"))?;
                 board[row][col] = num;
                 board[0][col] += 1;
                 board[row][0] += 1;
                 board[0][0] += num;
-            });
-        BingoBoard { board }
+            }
+        }
+        Ok(BingoBoard { board })
     }
-}
 
-impl BingoBoard {
-    fn has_won(&self) -> bool {
+    /// Whether every number of some row or column is marked.
+    pub fn has_won(&self) -> bool {
         for i in 1..=5 {
             if self.board[i][0] == 0 || self.board[0][i] == 0 {
                 return true;
@@ -124,11 +180,13 @@ impl BingoBoard {
         false
     }
 
-    fn sum(&self) -> u32 {
+    /// The sum of the unmarked numbers.
+    pub fn sum(&self) -> u32 {
         self.board[0][0]
     }
 
-    fn update(&mut self, num: u32) -> () {
+    /// Marks `num` wherever it appears on the board.
+    pub fn update(&mut self, num: u32) {
         for row in 1..=5 {
             for col in 1..=5 {
                 if self.board[row][col] == num {
@@ -141,20 +199,139 @@ impl BingoBoard {
     }
 }
 
+/// Generates `size` boards of distinct numbers below 100, drawing every
+/// number below 100 so that every board wins eventually.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    let mut nums = (0..100).collect::<Vec<u32>>();
+    rng.shuffle(&mut nums);
+    let mut input = nums
+        .iter()
+        .map(|num| num.to_string())
+        .collect::<Vec<String>>()
+        .join(",");
+    for _ in 0..size.max(1) {
+        rng.shuffle(&mut nums);
+        input.push('\n');
+        for row in nums[..25].chunks(5) {
+            input.push('\n');
+            input.push_str(
+                &row.iter()
+                    .map(|num| format!("{:>2}", num))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            );
+        }
+    }
+    input
+}
+
+fn reference_1(input: &str) -> Option<Answer> {
+    Some(Answer::from(*reference_scores(input)?.first()?))
+}
+
+fn reference_2(input: &str) -> Option<Answer> {
+    Some(Answer::from(*reference_scores(input)?.last()?))
+}
+
+/// The score of every board in the order the boards win, found by checking
+/// every row and column of every board against all the numbers drawn so far.
+/// Numbers are drawn at most once and are unique on a board, so inputs that
+/// repeat one aren't puzzle inputs.
+fn reference_scores(input: &str) -> Option<Vec<u32>> {
+    let mut blocks = input.split("\n\n");
+    let draws = blocks
+        .next()?
+        .split(',')
+        .map(|num| num.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    let boards = blocks
+        .map(|block| {
+            let rows = block
+                .lines()
+                .map(|line| {
+                    line.split_whitespace()
+                        .map(|num| num.parse::<u32>().ok())
+                        .collect::<Option<Vec<u32>>>()
+                        .filter(|row| row.len() == 5)
+                })
+                .collect::<Option<Vec<Vec<u32>>>>()?;
+            (rows.len() == 5).then_some(rows)
+        })
+        .collect::<Option<Vec<Vec<Vec<u32>>>>>()?;
+    let distinct = |nums: &[u32]| {
+        nums.iter()
+            .enumerate()
+            .all(|(i, num)| !nums[..i].contains(num))
+    };
+    if !distinct(&draws) || !boards.iter().all(|board| distinct(&board.concat())) {
+        return None;
+    }
+    let mut won = vec![false; boards.len()];
+    let mut scores = Vec::new();
+    for (n, draw) in draws.iter().enumerate() {
+        let marked = |num: &u32| draws[..=n].contains(num);
+        for (board, won) in boards.iter().zip(won.iter_mut()) {
+            let bingo = (0..5)
+                .any(|i| board[i].iter().all(marked) || board.iter().all(|row| marked(&row[i])));
+            if bingo && !*won {
+                *won = true;
+                let unmarked = board
+                    .iter()
+                    .flatten()
+                    .filter(|num| !marked(num))
+                    .sum::<u32>();
+                scores.push(unmarked * draw);
+            }
+        }
+    }
+    Some(scores)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::advent::examples::example;
     use rstest::rstest;
 
     #[rstest]
-    #[case("7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n\n 3 15  0  2 22\n 9 18 13 17  5\n19  8  7 25 23\n20 11 10 24  4\n14 21 16 12  6\n\n14 21 17 24  4\n10 16 15  9 19\n18  8 23 26 20\n22 11 13  6  5\n 2  0 12  3  7")]
-    fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "4512");
+    #[case("example_1")]
+    fn test_part_1(#[case] name: &str) {
+        let example = example(2021, 4, name);
+        assert_eq!(
+            part_1(&example.input),
+            Ok(Answer::parse(&example.part1.unwrap()))
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "1,2,3,4,0,10,11,12,13,14\n\n\
+         \x201  2  3  4  0\n20 21 22 23 24\n25 26 27 28 29\n30 31 32 33 34\n35 36 37 38 39\n\n\
+         10 11 12 13 14\n40 41 42 43 44\n45 46 47 48 49\n50 51 52 53 54\n55 56 57 58 59",
+        0
+    )]
+    fn test_part_1_zero_score(#[case] input: &str, #[case] expected: u32) {
+        assert_eq!(part_1(input), Ok(Answer::from(expected)));
+    }
+
+    #[rstest]
+    #[case("example_1")]
+    fn test_part_2(#[case] name: &str) {
+        let example = example(2021, 4, name);
+        assert_eq!(
+            part_2(&example.input),
+            Ok(Answer::parse(&example.part2.unwrap()))
+        );
     }
 
     #[rstest]
-    #[case("7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n\n 3 15  0  2 22\n 9 18 13 17  5\n19  8  7 25 23\n20 11 10 24  4\n14 21 16 12  6\n\n14 21 17 24  4\n10 16 15  9 19\n18  8 23 26 20\n22 11 13  6  5\n 2  0 12  3  7")]
-    fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "1924");
+    #[case(
+        "1,2\n\n\
+         \x201  3  4  5  6\n7  8  9 10 11\n12 13 14 15 16\n17 18 19 20 21\n22 23 24 25 26"
+    )]
+    fn test_no_board_wins(#[case] input: &str) {
+        let expected = Err(AdventError::no_solution("no board ever wins"));
+        assert_eq!(part_1(input), expected);
+        assert_eq!(part_2(input), expected);
     }
 }
@@ -1,5 +1,42 @@
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{AdventError, Answer, Reference, Rng, Solver};
+
+pub struct CalorieCounting;
+
+impl Solver for CalorieCounting {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// The jungle must be too overgrown and difficult to navigate in vehicles or
@@ -15,35 +52,80 @@ pub fn solve(input: &str) -> (String, String) {
 ///
 /// PART 1 : Find the Elf carrying the most Calories. How many total Calories
 /// is that Elf carrying?
-fn part_1(input: &str) -> String {
-    let max_elf = parse_input(input)
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let max_elf = parse_input(input)?
         .into_iter()
         .max()
-        .expect("input has no maximum");
-    format!("{}", max_elf)
+        .ok_or_else(|| AdventError::no_solution("input has no maximum"))?;
+    Ok(Answer::from(max_elf))
 }
 
 /// PART 2 : Find the top three Elves carrying the most Calories. How many
 /// Calories are those Elves carrying in total?
-fn part_2(input: &str) -> String {
-    let mut elves = parse_input(input);
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let mut elves = parse_input(input)?;
+    if elves.len() < 3 {
+        return Err(AdventError::no_solution("input has fewer than three elves"));
+    }
     elves.sort_by(|a, b| b.cmp(a));
-    format!("{}", elves[..3].iter().sum::<u32>())
+    Ok(Answer::from(elves[..3].iter().sum::<u32>()))
+}
+
+fn parse_input(input: &str) -> Result<Vec<u32>, AdventError> {
+    input
+        .lines()
+        .enumerate()
+        .try_fold(vec![0], |mut elves, (idx, line)| {
+            if line.is_empty() {
+                elves.push(0);
+            } else if let Some(elf) = elves.last_mut() {
+                *elf += line.parse::<u32>().map_err(|_| {
+                    AdventError::parse(idx, 0, line, "could not parse line as u32 of calories")
+                })?;
+            }
+            Ok(elves)
+        })
+}
+
+/// Generates the snacks of `size` elves, at least the three part 2 needs.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(3))
+        .map(|_| {
+            (0..rng.range(1..=10))
+                .map(|_| rng.range(1000..=60000).to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n")
+}
+
+/// Compares the total of every elf with every other.
+fn reference_1(input: &str) -> Option<Answer> {
+    reference_input(input)?.into_iter().max().map(Answer::from)
+}
+
+/// Takes the elf with the largest total three times over.
+fn reference_2(input: &str) -> Option<Answer> {
+    let mut elves = reference_input(input)?;
+    (0..3)
+        .map(|_| {
+            let (top, _) = elves.iter().enumerate().max_by_key(|(_, total)| **total)?;
+            Some(elves.swap_remove(top))
+        })
+        .sum::<Option<u32>>()
+        .map(Answer::from)
 }
 
-fn parse_input(input: &str) -> Vec<u32> {
+fn reference_input(input: &str) -> Option<Vec<u32>> {
     input
         .split("\n\n")
-        .map(|elf_inventory| {
-            elf_inventory
-                .lines()
-                .map(|line| {
-                    line.parse::<u32>()
-                        .expect("could not parse line as u32 of calories")
-                })
-                .sum::<u32>()
+        .map(|elf| {
+            elf.lines()
+                .map(|line| line.parse::<u32>().ok())
+                .sum::<Option<u32>>()
         })
-        .collect::<Vec<u32>>()
+        .collect::<Option<Vec<u32>>>()
 }
 
 #[cfg(test)]
@@ -54,12 +136,12 @@ mod tests {
     #[rstest]
     #[case("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "24000");
+        assert_eq!(part_1(input), Ok(Answer::from(24000)));
     }
 
     #[rstest]
     #[case("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000")]
     fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "45000");
+        assert_eq!(part_2(input), Ok(Answer::from(45000)));
     }
 }
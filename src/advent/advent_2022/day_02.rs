@@ -1,8 +1,45 @@
 use std::cmp::Ordering;
 use std::str::FromStr;
 
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{split_columns, AdventError, Answer, Reference, Rng, Solver};
+
+pub struct RockPaperScissors;
+
+impl Solver for RockPaperScissors {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// Appreciative of your help yesterday, one Elf gives you an encrypted strategy
@@ -23,14 +60,14 @@ pub fn solve(input: &str) -> (String, String) {
 ///
 /// PART 1 : What would your total score be if everything goes exactly according
 /// to your strategy guide?
-fn part_1(input: &str) -> String {
-    let total_score = parse_input(input)
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let total_score = parse_input(input)?
         .into_iter()
         .map(|(opp_throw, player_key)| (opp_throw, Throw::from(&player_key)))
         .fold(0, |score, (opp_throw, player_throw)| {
             score + scoring_of(&opp_throw, &player_throw)
         });
-    format!("{}", total_score)
+    Ok(Answer::from(total_score))
 }
 
 /// The Elf finishes helping with the tent and sneaks back over to you. "Anyway,
@@ -39,8 +76,8 @@ fn part_1(input: &str) -> String {
 ///
 /// PART 2 : Following the Elf's instructions for the second column, what would
 /// your total score be if everything goes exactly according to your strategy guide?
-fn part_2(input: &str) -> String {
-    let total_score = parse_input(input)
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let total_score = parse_input(input)?
         .into_iter()
         .map(|(opp_throw, player_key)| {
             (
@@ -51,30 +88,33 @@ fn part_2(input: &str) -> String {
         .fold(0, |score, (opp_throw, player_throw)| {
             score + scoring_of(&opp_throw, &player_throw)
         });
-    format!("{}", total_score)
+    Ok(Answer::from(total_score))
 }
 
-fn parse_input(input: &str) -> Vec<(Throw, Key)> {
+fn parse_input(input: &str) -> Result<Vec<(Throw, Key)>, AdventError> {
     input
         .lines()
-        .map(|line| {
-            let parts = line.split(" ").collect::<Vec<&str>>();
-            assert_eq!(
-                parts.len(),
-                2,
-                "could not parse {:?} as two space-delimited throws",
-                line
-            );
-            (
-                parts[0]
+        .enumerate()
+        .map(|(idx, line)| {
+            let parts = split_columns(line, ' ');
+            if parts.len() != 2 {
+                return Err(AdventError::parse(
+                    idx,
+                    0,
+                    line,
+                    "could not parse line as two space-delimited throws",
+                ));
+            }
+            let ((throw_col, throw), (key_col, key)) = (parts[0], parts[1]);
+            Ok((
+                throw
                     .parse::<Throw>()
-                    .expect("could not parse Throw from line"),
-                parts[1]
-                    .parse::<Key>()
-                    .expect("could not parse Key from line"),
-            )
+                    .map_err(|reason| AdventError::parse(idx, throw_col, throw, reason))?,
+                key.parse::<Key>()
+                    .map_err(|reason| AdventError::parse(idx, key_col, key, reason))?,
+            ))
         })
-        .collect::<Vec<(Throw, Key)>>()
+        .collect::<Result<Vec<(Throw, Key)>, AdventError>>()
 }
 
 fn scoring_of(opp_throw: &Throw, player_throw: &Throw) -> u32 {
@@ -145,9 +185,9 @@ impl From<(&Throw, &Ordering)> for Throw {
             Ordering::Less => match opp_throw {
                 Throw::Rock => Throw::Scissors,
                 Throw::Paper => Throw::Rock,
-                Throw::Scissors => Throw::Rock,
+                Throw::Scissors => Throw::Paper,
             },
-            Ordering::Equal => opp_throw.clone(),
+            Ordering::Equal => *opp_throw,
             Ordering::Greater => match opp_throw {
                 Throw::Rock => Throw::Paper,
                 Throw::Paper => Throw::Scissors,
@@ -190,6 +230,71 @@ impl From<&Key> for Ordering {
     }
 }
 
+/// Generates `size` rounds of the strategy guide, at least one.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&["A", "B", "C"]),
+                rng.pick(&["X", "Y", "Z"])
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Looks every round up in the table of scores when the second column is the
+/// throw to make.
+fn reference_1(input: &str) -> Option<Answer> {
+    reference_score(
+        input,
+        &[
+            ("A X", 4),
+            ("A Y", 8),
+            ("A Z", 3),
+            ("B X", 1),
+            ("B Y", 5),
+            ("B Z", 9),
+            ("C X", 7),
+            ("C Y", 2),
+            ("C Z", 6),
+        ],
+    )
+}
+
+/// Looks every round up in the table of scores when the second column is
+/// whether to lose, draw or win.
+fn reference_2(input: &str) -> Option<Answer> {
+    reference_score(
+        input,
+        &[
+            ("A X", 3),
+            ("A Y", 4),
+            ("A Z", 8),
+            ("B X", 1),
+            ("B Y", 5),
+            ("B Z", 9),
+            ("C X", 2),
+            ("C Y", 6),
+            ("C Z", 7),
+        ],
+    )
+}
+
+fn reference_score(input: &str, scores: &[(&str, u32)]) -> Option<Answer> {
+    input
+        .lines()
+        .map(|line| {
+            scores
+                .iter()
+                .find(|(round, _)| *round == line)
+                .map(|(_, score)| *score)
+        })
+        .sum::<Option<u32>>()
+        .map(Answer::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,12 +303,13 @@ mod tests {
     #[rstest]
     #[case("A Y\nB X\nC Z")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "15");
+        assert_eq!(part_1(input), Ok(Answer::from(15)));
     }
 
     #[rstest]
-    #[case("A Y\nB X\nC Z")]
-    fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "12");
+    #[case("A Y\nB X\nC Z", 12)]
+    #[case("C X", 2)]
+    fn test_part_2(#[case] input: &str, #[case] expected: u32) {
+        assert_eq!(part_2(input), Ok(Answer::from(expected)));
     }
 }
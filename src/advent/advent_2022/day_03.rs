@@ -1,8 +1,45 @@
 use std::collections::HashSet;
 use std::str::Chars;
 
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{AdventError, Answer, Reference, Rng, Solver};
+
+pub struct RucksackReorganization;
+
+impl Solver for RucksackReorganization {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// Each rucksack has two large compartments. All items of a given type are meant
@@ -26,13 +63,13 @@ pub fn solve(input: &str) -> (String, String) {
 ///
 /// PART 1 : Find the item type that appears in both compartments of each
 /// rucksack. What is the sum of the priorities of those item types?
-fn part_1(input: &str) -> String {
-    let priority_sum = parse_input(input)
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let priority_sum = parse_input(input)?
         .into_iter()
         .map(|sack| sack.intersect_compartments())
         .map(|item| priority_of(&item))
         .sum::<u32>();
-    format!("{}", priority_sum)
+    Ok(Answer::from(priority_sum))
 }
 
 /// For safety, the Elves are divided into groups of three. Every Elf carries a
@@ -51,20 +88,21 @@ fn part_1(input: &str) -> String {
 ///
 /// PART 2 : Find the item type that corresponds to the badges of each three-Elf
 /// group. What is the sum of the priorities of those item types?
-fn part_2(input: &str) -> String {
-    let priority_sum = parse_input(input)
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let priority_sum = parse_input(input)?
         .chunks(3)
         .map(|chunk| intersect_chunk(chunk))
         .map(|badge| priority_of(&badge))
         .sum::<u32>();
-    format!("{}", priority_sum)
+    Ok(Answer::from(priority_sum))
 }
 
-fn parse_input(input: &str) -> Vec<Rucksack> {
+fn parse_input(input: &str) -> Result<Vec<Rucksack<'_>>, AdventError> {
     input
         .lines()
-        .map(|line| Rucksack::from(line))
-        .collect::<Vec<Rucksack>>()
+        .enumerate()
+        .map(|(idx, line)| Rucksack::parse(idx, line))
+        .collect::<Result<Vec<Rucksack>, AdventError>>()
 }
 
 fn intersect_chunk(chunk: &[Rucksack]) -> String {
@@ -88,9 +126,9 @@ fn priority_of(items: &str) -> u32 {
     items
         .chars()
         .map(|ch| {
-            if 'a' <= ch && ch <= 'z' {
+            if ch.is_ascii_lowercase() {
                 (ch as u32) - ('a' as u32) + 1
-            } else if 'A' <= ch && ch <= 'Z' {
+            } else if ch.is_ascii_uppercase() {
                 (ch as u32) - ('A' as u32) + 27
             } else {
                 0
@@ -104,13 +142,30 @@ struct Rucksack<'a> {
     pub items: &'a str,
 }
 
-impl<'a> From<&'a str> for Rucksack<'a> {
-    fn from(items: &'a str) -> Rucksack<'a> {
-        Rucksack { items }
+impl<'a> Rucksack<'a> {
+    fn parse(idx: usize, items: &'a str) -> Result<Rucksack<'a>, AdventError> {
+        if let Some((col, ch)) = items
+            .char_indices()
+            .find(|(_, ch)| !ch.is_ascii_alphabetic())
+        {
+            return Err(AdventError::parse(
+                idx,
+                col,
+                &items[col..(col + ch.len_utf8())],
+                "item types should be ascii letters",
+            ));
+        }
+        if !items.len().is_multiple_of(2) {
+            return Err(AdventError::parse(
+                idx,
+                0,
+                items,
+                "compartments should hold the same number of items",
+            ));
+        }
+        Ok(Rucksack { items })
     }
-}
 
-impl Rucksack<'_> {
     fn intersect_compartments(&self) -> String {
         let left_set = self.left().collect::<HashSet<char>>();
         self.right()
@@ -133,6 +188,77 @@ impl Rucksack<'_> {
     }
 }
 
+/// Generates `size` rucksacks, rounded up to whole groups of three and at
+/// least one group. The compartments of each rucksack share exactly one item
+/// type, and the rucksacks of each group share exactly one badge.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    let mut rucksacks = Vec::new();
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<char>>();
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        // Each elf of the group packs from its own 17 item types, so the
+        // badge is the only item type the group has in common.
+        for pool in items[1..].chunks(17) {
+            let (shared, (left_pool, right_pool)) = (pool[0], pool[1..].split_at(8));
+            let len = rng.range(2..=16) as usize;
+            let mut left = (2..len)
+                .map(|_| *rng.pick(left_pool))
+                .chain([shared, badge])
+                .collect::<Vec<char>>();
+            let mut right = (1..len)
+                .map(|_| *rng.pick(right_pool))
+                .chain([shared])
+                .collect::<Vec<char>>();
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            rucksacks.push(left.into_iter().chain(right).collect::<String>());
+        }
+    }
+    rucksacks.join("\n")
+}
+
+/// Checks every item type against both compartments of every rucksack.
+fn reference_1(input: &str) -> Option<Answer> {
+    let priority_sum = reference_input(input)?
+        .into_iter()
+        .map(|items| {
+            let (left, right) = items.split_at(items.len() / 2);
+            reference_priorities(|item| left.contains(item) && right.contains(item))
+        })
+        .sum::<usize>();
+    Some(Answer::from(priority_sum))
+}
+
+/// Checks every item type against every rucksack of each group.
+fn reference_2(input: &str) -> Option<Answer> {
+    let priority_sum = reference_input(input)?
+        .chunks(3)
+        .map(|group| reference_priorities(|item| group.iter().all(|items| items.contains(item))))
+        .sum::<usize>();
+    Some(Answer::from(priority_sum))
+}
+
+/// The sum of the priorities of the item types that are `shared`.
+fn reference_priorities(shared: impl Fn(char) -> bool) -> usize {
+    ('a'..='z')
+        .chain('A'..='Z')
+        .zip(1..)
+        .filter(|(item, _)| shared(*item))
+        .map(|(_, priority)| priority)
+        .sum::<usize>()
+}
+
+fn reference_input(input: &str) -> Option<Vec<&str>> {
+    input
+        .lines()
+        .map(|items| {
+            (items.len() % 2 == 0 && items.chars().all(|item| item.is_ascii_alphabetic()))
+                .then_some(items)
+        })
+        .collect::<Option<Vec<&str>>>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,12 +267,12 @@ mod tests {
     #[rstest]
     #[case("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "157");
+        assert_eq!(part_1(input), Ok(Answer::from(157)));
     }
 
     #[rstest]
     #[case("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw")]
     fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "70");
+        assert_eq!(part_2(input), Ok(Answer::from(70)));
     }
 }
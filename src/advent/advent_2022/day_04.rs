@@ -1,7 +1,44 @@
 use std::ops::RangeInclusive;
 
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{split_columns, AdventError, Answer, Reference, Rng, Solver};
+
+pub struct CampCleanup;
+
+impl Solver for CampCleanup {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(parse_input(input).map(|_| ()))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// Space needs to be cleared before the last supplies can be unloaded from the
@@ -15,8 +52,8 @@ pub fn solve(input: &str) -> (String, String) {
 /// list of the section assignments for each pair (your puzzle input).
 ///
 /// PART 1 : In how many assignment pairs does one range fully contain the r2?
-fn part_1(input: &str) -> String {
-    let num_redundant_pairs = parse_input(input)
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let num_redundant_pairs = parse_input(input)?
         .into_iter()
         .fold(0, |num_pairs, (r1, r2)| {
             match intersect_range(r1.clone(), r2.clone()) {
@@ -30,54 +67,69 @@ fn part_1(input: &str) -> String {
                 None => num_pairs,
             }
         });
-    format!("{}", num_redundant_pairs)
+    Ok(Answer::from(num_redundant_pairs))
 }
 
 /// It seems like there is still quite a bit of duplicate work planned. Instead, the
 /// Elves would like to know the number of pairs that overlap at all.
 ///
 /// PART 2 : In how many assignment pairs do the ranges overlap?
-fn part_2(input: &str) -> String {
-    let num_overlapping_pairs = parse_input(input)
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let num_overlapping_pairs = parse_input(input)?
         .into_iter()
         .fold(0, |num_pairs, (r1, r2)| match intersect_range(r1, r2) {
             Some(_) => num_pairs + 1,
             None => num_pairs,
         });
-    format!("{}", num_overlapping_pairs)
+    Ok(Answer::from(num_overlapping_pairs))
 }
 
-fn parse_input(input: &str) -> Vec<(RangeInclusive<usize>, RangeInclusive<usize>)> {
+type AssignmentPair = (RangeInclusive<usize>, RangeInclusive<usize>);
+
+fn parse_input(input: &str) -> Result<Vec<AssignmentPair>, AdventError> {
     input
         .lines()
-        .map(|line| {
-            let parts = line.split(",").collect::<Vec<&str>>();
-            assert_eq!(
-                parts.len(),
-                2,
-                "could not parse line as two comma-delimited assignments"
-            );
-            (parse_range(parts[0]), parse_range(parts[1]))
+        .enumerate()
+        .map(|(idx, line)| {
+            let parts = split_columns(line, ',');
+            if parts.len() != 2 {
+                return Err(AdventError::parse(
+                    idx,
+                    0,
+                    line,
+                    "could not parse line as two comma-delimited assignments",
+                ));
+            }
+            Ok((parse_range(idx, parts[0])?, parse_range(idx, parts[1])?))
         })
-        .collect::<Vec<(RangeInclusive<usize>, RangeInclusive<usize>)>>()
+        .collect::<Result<Vec<AssignmentPair>, AdventError>>()
 }
 
-fn parse_range(input: &str) -> RangeInclusive<usize> {
-    let parts = input.split("-").collect::<Vec<&str>>();
-    assert_eq!(
-        parts.len(),
-        2,
-        "could not parse line as two hyphen-delimited intervals"
-    );
-    parts[0]
-        .parse::<usize>()
-        .expect("could not parse line as u32 of start")
-        ..=parts[1]
-            .parse::<usize>()
-            .expect("could not parse line as u32 of end")
+/// Parses the assignment found at byte `offset` of the `idx`-th line.
+fn parse_range(
+    idx: usize,
+    (offset, input): (usize, &str),
+) -> Result<RangeInclusive<usize>, AdventError> {
+    let parts = split_columns(input, '-');
+    if parts.len() != 2 {
+        return Err(AdventError::parse(
+            idx,
+            offset,
+            input,
+            "could not parse assignment as two hyphen-delimited intervals",
+        ));
+    }
+    let bound = |(col, part): (usize, &str), reason| {
+        part.parse::<usize>()
+            .map_err(|_| AdventError::parse(idx, offset + col, part, reason))
+    };
+    let start = bound(parts[0], "could not parse usize of start")?;
+    let end = bound(parts[1], "could not parse usize of end")?;
+    Ok(start..=end)
 }
 
-fn intersect_range<T: PartialOrd>(
+/// The overlap of two inclusive ranges, or `None` if they don't overlap.
+pub fn intersect_range<T: PartialOrd>(
     r1: RangeInclusive<T>,
     r2: RangeInclusive<T>,
 ) -> Option<RangeInclusive<T>> {
@@ -96,6 +148,57 @@ fn intersect_range<T: PartialOrd>(
     }
 }
 
+/// Generates `size` pairs of section assignments between 1 and 99, at least
+/// one.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let mut range = || {
+                let start = rng.range(1..=99);
+                format!("{}-{}", start, rng.range(start..=99))
+            };
+            format!("{},{}", range(), range())
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Checks whether every section of one assignment is in the other.
+fn reference_1(input: &str) -> Option<Answer> {
+    let num_redundant_pairs = reference_input(input)?
+        .into_iter()
+        .filter(|(r1, r2)| {
+            r1.clone().all(|section| r2.contains(&section))
+                || r2.clone().all(|section| r1.contains(&section))
+        })
+        .count();
+    Some(Answer::from(num_redundant_pairs))
+}
+
+/// Checks whether any section of one assignment is in the other.
+fn reference_2(input: &str) -> Option<Answer> {
+    let num_overlapping_pairs = reference_input(input)?
+        .into_iter()
+        .filter(|(r1, r2)| r1.clone().any(|section| r2.contains(&section)))
+        .count();
+    Some(Answer::from(num_overlapping_pairs))
+}
+
+fn reference_input(input: &str) -> Option<Vec<AssignmentPair>> {
+    let range = |assignment: &str| {
+        let (start, end) = assignment.split_once('-')?;
+        let (start, end) = (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?);
+        (start <= end).then_some(start..=end)
+    };
+    input
+        .lines()
+        .map(|line| {
+            let (r1, r2) = line.split_once(',')?;
+            Some((range(r1)?, range(r2)?))
+        })
+        .collect::<Option<Vec<AssignmentPair>>>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,12 +207,29 @@ mod tests {
     #[rstest]
     #[case("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8")]
     fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "2");
+        assert_eq!(part_1(input), Ok(Answer::from(2)));
     }
 
     #[rstest]
     #[case("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8")]
     fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "4");
+        assert_eq!(part_2(input), Ok(Answer::from(4)));
+    }
+
+    #[rstest]
+    #[case("2-4,6-x", 1, 7, "x")]
+    #[case("2-4,6-8\n2-3,y-5", 2, 5, "y")]
+    #[case("2-4,6", 1, 5, "6")]
+    fn test_parse_error(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        assert!(matches!(
+            part_1(input),
+            Err(AdventError::Parse { line: l, column: c, text: t, .. })
+                if l == line && c == column && t == text
+        ));
     }
 }
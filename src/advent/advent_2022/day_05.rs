@@ -1,5 +1,46 @@
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{split_columns, AdventError, Answer, Reference, Rng, Solver};
+
+pub struct SupplyStacks;
+
+impl Solver for SupplyStacks {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<(), AdventError>> {
+        Some(
+            parse_input_stacks(input)
+                .and(parse_input_queries(input))
+                .map(|_| ()),
+        )
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// The ship has a giant cargo crane capable of moving crates between stacks.
@@ -12,15 +53,15 @@ pub fn solve(input: &str) -> (String, String) {
 /// want to be ready to unload them as soon as possible so they can embark.
 /// PART 1 : After the rearrangement procedure completes, what crate ends up
 /// on top of each stack?
-fn part_1(input: &str) -> String {
-    let mut stacks = parse_input_stacks(input);
-    parse_input_queries(input).iter().for_each(|query| {
-        (0..query.0).for_each(|_| {
-            let ch = stacks[query.1 - 1].pop().expect("cargo stack is empty");
-            stacks[query.2 - 1].push(ch);
-        });
-    });
-    format!("{}", skim_top(&stacks))
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let mut stacks = parse_input_stacks(input)?;
+    for query in parse_input_queries(input)? {
+        for _ in 0..query.0 {
+            let ch = pop_crate(&mut stacks, query.1)?;
+            stack_at(&mut stacks, query.2)?.push(ch);
+        }
+    }
+    Ok(Answer::from(skim_top(&stacks)))
 }
 
 /// Some mud was covering the writing on the side of the crane, and you quickly
@@ -32,24 +73,24 @@ fn part_1(input: &str) -> String {
 ///
 /// PART 2 : After the rearrangement procedure completes, what crate ends up
 /// on top of each stack?
-fn part_2(input: &str) -> String {
-    let mut stacks = parse_input_stacks(input);
-    parse_input_queries(input).iter().for_each(|query| {
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let mut stacks = parse_input_stacks(input)?;
+    for query in parse_input_queries(input)? {
         let mut crane = (0..query.0)
-            .map(|_| stacks[query.1 - 1].pop().expect("cargo stack is empty"))
-            .collect::<Vec<char>>();
-        while !crane.is_empty() {
-            stacks[query.2 - 1].push(crane.pop().expect("crane is empty"));
+            .map(|_| pop_crate(&mut stacks, query.1))
+            .collect::<Result<Vec<char>, AdventError>>()?;
+        while let Some(cargo_crate) = crane.pop() {
+            stack_at(&mut stacks, query.2)?.push(cargo_crate);
         }
-    });
-    format!("{}", skim_top(&stacks))
+    }
+    Ok(Answer::from(skim_top(&stacks)))
 }
 
-fn parse_input_stacks(input: &str) -> Vec<Vec<char>> {
-    input
+fn parse_input_stacks(input: &str) -> Result<Vec<Vec<char>>, AdventError> {
+    Ok(input
         .split("\n\n")
-        .nth(0)
-        .expect("input txt is empty")
+        .next()
+        .ok_or_else(|| AdventError::no_solution("input txt is empty"))?
         .lines()
         .rev()
         .skip(1)
@@ -67,63 +108,235 @@ fn parse_input_stacks(input: &str) -> Vec<Vec<char>> {
             cargo
                 .into_iter()
                 .enumerate()
-                .filter(|(_, cargo_crate)| cargo_crate.is_some())
+                .filter_map(|(i, cargo_crate)| cargo_crate.map(|ch| (i, ch)))
                 .for_each(|(i, cargo_crate)| {
                     while stacks.len() <= i {
                         stacks.push(Vec::new());
                     }
-                    stacks[i].push(cargo_crate.expect("could not unwrap crate"));
+                    stacks[i].push(cargo_crate);
                 });
             stacks
-        })
+        }))
 }
 
-fn parse_input_queries(input: &str) -> Vec<(usize, usize, usize)> {
-    input
-        .split("\n\n")
-        .nth(1)
-        .expect("input txt has no queries")
+fn parse_input_queries(input: &str) -> Result<Vec<(usize, usize, usize)>, AdventError> {
+    let mut sections = input.split("\n\n");
+    let offset = sections
+        .next()
+        .map_or(0, |section| section.lines().count() + 1);
+    sections
+        .next()
+        .ok_or_else(|| AdventError::no_solution("input txt has no queries"))?
         .lines()
-        .map(|line| {
-            let parts = line.split(" ").collect::<Vec<&str>>();
-            assert_eq!(parts.len(), 6, "query is not properly formed");
-            (
-                parts[1]
-                    .parse::<usize>()
-                    .expect("could not parse usize of cargo swap size"),
-                parts[3]
-                    .parse::<usize>()
-                    .expect("could not parse usize of origin stack"),
-                parts[5]
-                    .parse::<usize>()
-                    .expect("could not parse usize of dest stack"),
-            )
+        .enumerate()
+        .map(|(idx, line)| {
+            let parts = split_columns(line, ' ');
+            if parts.len() != 6 {
+                return Err(AdventError::parse(
+                    offset + idx,
+                    0,
+                    line,
+                    "query is not properly formed",
+                ));
+            }
+            let parse_part = |(col, part): (usize, &str), reason: &str| {
+                part.parse::<usize>()
+                    .map_err(|_| AdventError::parse(offset + idx, col, part, reason))
+            };
+            let origin = parse_part(parts[3], "could not parse usize of origin stack")?;
+            let dest = parse_part(parts[5], "could not parse usize of dest stack")?;
+            if origin == 0 || dest == 0 {
+                let (col, part) = if origin == 0 { parts[3] } else { parts[5] };
+                return Err(AdventError::parse(
+                    offset + idx,
+                    col,
+                    part,
+                    "stacks are numbered from 1",
+                ));
+            }
+            Ok((
+                parse_part(parts[1], "could not parse usize of cargo swap size")?,
+                origin,
+                dest,
+            ))
         })
-        .collect::<Vec<(usize, usize, usize)>>()
+        .collect::<Result<Vec<(usize, usize, usize)>, AdventError>>()
+}
+
+fn stack_at(stacks: &mut [Vec<char>], n: usize) -> Result<&mut Vec<char>, AdventError> {
+    stacks
+        .get_mut(n - 1)
+        .ok_or_else(|| AdventError::NoSolution(format!("there is no cargo stack {}", n)))
+}
+
+fn pop_crate(stacks: &mut [Vec<char>], n: usize) -> Result<char, AdventError> {
+    stack_at(stacks, n)?
+        .pop()
+        .ok_or_else(|| AdventError::NoSolution(format!("cargo stack {} is empty", n)))
 }
 
-fn skim_top(stacks: &Vec<Vec<char>>) -> String {
+fn skim_top(stacks: &[Vec<char>]) -> String {
     stacks
         .iter()
-        .filter(|stack| !stack.is_empty())
-        .map(|stack| stack.last().expect("cargo stack has no last element"))
+        .filter_map(|stack| stack.last())
         .collect::<String>()
 }
 
+/// Generates nine stacks of crates and `size` moves, at least one, none of
+/// which moves more crates than its origin stack holds.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    let mut stacks = (0..9)
+        .map(|_| {
+            (0..rng.range(1..=8))
+                .map(|_| (b'A' + rng.range(0..=25) as u8) as char)
+                .collect::<Vec<char>>()
+        })
+        .collect::<Vec<Vec<char>>>();
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+    let mut input = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| {
+                    stack
+                        .get(level)
+                        .map_or(String::from("   "), |ch| format!("[{}]", ch))
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .chain([(1..=9)
+            .map(|n| format!(" {} ", n))
+            .collect::<Vec<String>>()
+            .join(" ")])
+        .collect::<Vec<String>>()
+        .join("\n");
+    input.push('\n');
+    for _ in 0..size.max(1) {
+        let origin = loop {
+            let origin = rng.below(9);
+            if !stacks[origin].is_empty() {
+                break origin;
+            }
+        };
+        let dest = (origin + 1 + rng.below(8)) % 9;
+        let len = stacks[origin].len();
+        let count = rng.range(1..=len as u64) as usize;
+        let moved = stacks[origin].split_off(len - count);
+        stacks[dest].extend(moved);
+        input.push_str(&format!(
+            "\nmove {} from {} to {}",
+            count,
+            origin + 1,
+            dest + 1
+        ));
+    }
+    input
+}
+
+/// Moves the crates one at a time.
+fn reference_1(input: &str) -> Option<Answer> {
+    let (mut stacks, queries) = reference_input(input)?;
+    for (count, origin, dest) in queries {
+        for _ in 0..count {
+            let cargo_crate = stacks.get_mut(origin.checked_sub(1)?)?.pop()?;
+            stacks.get_mut(dest.checked_sub(1)?)?.push(cargo_crate);
+        }
+    }
+    Some(Answer::from(skim_top(&stacks)))
+}
+
+/// Lifts the crates one at a time and sets them down in reverse, so that
+/// they keep their order.
+fn reference_2(input: &str) -> Option<Answer> {
+    let (mut stacks, queries) = reference_input(input)?;
+    for (count, origin, dest) in queries {
+        let mut lifted = Vec::new();
+        for _ in 0..count {
+            lifted.push(stacks.get_mut(origin.checked_sub(1)?)?.pop()?);
+        }
+        while let Some(cargo_crate) = lifted.pop() {
+            stacks.get_mut(dest.checked_sub(1)?)?.push(cargo_crate);
+        }
+    }
+    Some(Answer::from(skim_top(&stacks)))
+}
+
+type Query = (usize, usize, usize);
+
+/// Reads the stacks a column of the drawing at a time, bottom up.
+fn reference_input(input: &str) -> Option<(Vec<Vec<char>>, Vec<Query>)> {
+    let (drawing, queries) = input.split_once("\n\n")?;
+    let mut rows = drawing.lines().collect::<Vec<&str>>();
+    let labels = rows.pop()?;
+    let mut stacks = vec![Vec::new(); labels.split_whitespace().count()];
+    for row in rows.iter().rev() {
+        for (n, stack) in stacks.iter_mut().enumerate() {
+            match row.chars().nth(4 * n + 1) {
+                Some(' ') | None => (),
+                Some(cargo_crate) => stack.push(cargo_crate),
+            }
+        }
+    }
+    let queries = queries
+        .lines()
+        .map(|line| match line.split(' ').collect::<Vec<&str>>()[..] {
+            ["move", count, "from", origin, "to", dest] => Some((
+                count.parse::<usize>().ok()?,
+                origin.parse::<usize>().ok()?,
+                dest.parse::<usize>().ok()?,
+            )),
+            _ => None,
+        })
+        .collect::<Option<Vec<Query>>>()?;
+    Some((stacks, queries))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::advent::examples::example;
     use rstest::rstest;
 
     #[rstest]
-    #[case("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2")]
-    fn test_part_1(#[case] input: &str) {
-        assert_eq!(part_1(input), "CMZ");
+    #[case("example_1")]
+    fn test_part_1(#[case] name: &str) {
+        let example = example(2022, 5, name);
+        assert_eq!(
+            part_1(&example.input),
+            Ok(Answer::parse(&example.part1.unwrap()))
+        );
+    }
+
+    #[rstest]
+    #[case("example_1")]
+    fn test_part_2(#[case] name: &str) {
+        let example = example(2022, 5, name);
+        assert_eq!(
+            part_2(&example.input),
+            Ok(Answer::parse(&example.part2.unwrap()))
+        );
+    }
+
+    #[rstest]
+    #[case("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove x from 1 to 3", 7, 6)]
+    #[case(
+        "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 0 to 1",
+        6,
+        13
+    )]
+    fn test_parse_error(#[case] input: &str, #[case] line: usize, #[case] column: usize) {
+        assert!(matches!(
+            part_1(input),
+            Err(AdventError::Parse { line: l, column: c, .. }) if l == line && c == column
+        ));
     }
 
     #[rstest]
-    #[case("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2")]
-    fn test_part_2(#[case] input: &str) {
-        assert_eq!(part_2(input), "MCD");
+    #[case("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 4 from 2 to 1")]
+    #[case("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 4 to 1")]
+    fn test_no_solution(#[case] input: &str) {
+        assert!(matches!(part_2(input), Err(AdventError::NoSolution(_))));
     }
 }
@@ -1,7 +1,40 @@
 use std::collections::HashSet;
 
-pub fn solve(input: &str) -> (String, String) {
-    (part_1(&input), part_2(&input))
+use crate::advent::{AdventError, Answer, Reference, Rng, Solver};
+
+pub struct TuningTrouble;
+
+impl Solver for TuningTrouble {
+    fn year(&self) -> u32 {
+        2022
+    }
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(generate_input(size, rng))
+    }
+
+    fn reference(&self) -> Option<Reference> {
+        Some(Reference {
+            part_1: reference_1,
+            part_2: reference_2,
+        })
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+        part_2(input)
+    }
 }
 
 /// As you move through the dense undergrowth, one of the Elves gives you a
@@ -30,9 +63,9 @@ pub fn solve(input: &str) -> (String, String) {
 ///
 /// PART 1 : How many characters need to be processed before the first
 /// start-of-packet marker is detected?
-fn part_1(input: &str) -> String {
-    let start_of_packet_marker = parse_input(input, 4);
-    format!("{}", start_of_packet_marker)
+fn part_1(input: &str) -> Result<Answer, AdventError> {
+    let start_of_packet_marker = parse_input(input, 4)?;
+    Ok(Answer::from(start_of_packet_marker))
 }
 
 /// Your device's communication system is correctly detecting packets, but
@@ -43,17 +76,26 @@ fn part_1(input: &str) -> String {
 ///
 /// PART 2 : How many characters need to be processed before the first
 /// start-of-message marker is detected?
-fn part_2(input: &str) -> String {
-    let start_of_message_marker = parse_input(input, 14);
-    format!("{}", start_of_message_marker)
+fn part_2(input: &str) -> Result<Answer, AdventError> {
+    let start_of_message_marker = parse_input(input, 14)?;
+    Ok(Answer::from(start_of_message_marker))
 }
 
-fn parse_input(input: &str, target_len: usize) -> usize {
-    assert!(
-        input.len() >= target_len,
-        "input should include at least {} characters",
-        target_len
-    );
+fn parse_input(input: &str, target_len: usize) -> Result<usize, AdventError> {
+    if input.len() < target_len {
+        return Err(AdventError::NoSolution(format!(
+            "input should include at least {} characters",
+            target_len
+        )));
+    }
+    if let Some((col, ch)) = input.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+        return Err(AdventError::parse(
+            0,
+            col,
+            &input[col..(col + ch.len_utf8())],
+            "datastream should only contain ascii characters",
+        ));
+    }
     (target_len..=input.len())
         .find(|n| {
             input[(n - target_len)..*n]
@@ -62,7 +104,45 @@ fn parse_input(input: &str, target_len: usize) -> usize {
                 .len()
                 == target_len
         })
-        .expect("no solution")
+        .ok_or_else(|| AdventError::no_solution("no marker in datastream"))
+}
+
+/// Generates a datastream of `size` characters, at least 14, from a handful
+/// of letters with a single run of 14 distinct ones somewhere in its back
+/// half, so that both markers are there to find.
+fn generate_input(size: usize, rng: &mut Rng) -> String {
+    let mut letters = ('a'..='z').collect::<Vec<char>>();
+    rng.shuffle(&mut letters);
+    let size = size.max(14);
+    let start = rng.range((size as u64 - 14) / 2..=size as u64 - 14) as usize;
+    (0..size)
+        .map(|i| match i.checked_sub(start) {
+            Some(offset) if offset < 14 => letters[offset],
+            _ => *rng.pick(&letters[14..20]),
+        })
+        .collect::<String>()
+}
+
+fn reference_1(input: &str) -> Option<Answer> {
+    reference_marker(input, 4)
+}
+
+fn reference_2(input: &str) -> Option<Answer> {
+    reference_marker(input, 14)
+}
+
+/// The end of the first `len` characters that differ from each other,
+/// comparing every pair of characters of every window.
+fn reference_marker(input: &str, len: usize) -> Option<Answer> {
+    let chars = input
+        .is_ascii()
+        .then(|| input.chars().collect::<Vec<char>>())?;
+    (len..=chars.len())
+        .find(|end| {
+            let window = &chars[(end - len)..*end];
+            (0..len).all(|i| ((i + 1)..len).all(|j| window[i] != window[j]))
+        })
+        .map(Answer::from)
 }
 
 #[cfg(test)]
@@ -71,22 +151,22 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", "7")]
-    #[case("bvwbjplbgvbhsrlpgdmjqwftvncz", "5")]
-    #[case("nppdvjthqldpwncqszvftbrmjlhg", "6")]
-    #[case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", "10")]
-    #[case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", "11")]
-    fn test_part_1(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(part_1(input), expected);
+    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7)]
+    #[case("bvwbjplbgvbhsrlpgdmjqwftvncz", 5)]
+    #[case("nppdvjthqldpwncqszvftbrmjlhg", 6)]
+    #[case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10)]
+    #[case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11)]
+    fn test_part_1(#[case] input: &str, #[case] expected: usize) {
+        assert_eq!(part_1(input), Ok(Answer::from(expected)));
     }
 
     #[rstest]
-    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", "19")]
-    #[case("bvwbjplbgvbhsrlpgdmjqwftvncz", "23")]
-    #[case("nppdvjthqldpwncqszvftbrmjlhg", "23")]
-    #[case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", "29")]
-    #[case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", "26")]
-    fn test_part_2(#[case] input: &str, #[case] expected: &str) {
-        assert_eq!(part_2(input), expected);
+    #[case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 19)]
+    #[case("bvwbjplbgvbhsrlpgdmjqwftvncz", 23)]
+    #[case("nppdvjthqldpwncqszvftbrmjlhg", 23)]
+    #[case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 29)]
+    #[case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26)]
+    fn test_part_2(#[case] input: &str, #[case] expected: usize) {
+        assert_eq!(part_2(input), Ok(Answer::from(expected)));
     }
 }
@@ -0,0 +1,143 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The answer to one part of a challenge. Numeric answers compare by value,
+/// whether they are signed or not, and order numerically; other answers
+/// compare by how they are displayed and have no order.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Int(i64),
+    Uint(u64),
+    Str(String),
+    /// A multi-line answer, such as letters drawn in ASCII art.
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    /// Reads an answer back from its displayed text, taking whole numbers as
+    /// numeric answers and text spanning several lines as lines.
+    pub fn parse(text: &str) -> Answer {
+        if text.contains('\n') {
+            Answer::Lines(text.lines().map(String::from).collect::<Vec<String>>())
+        } else if let Ok(n) = text.parse::<u64>() {
+            Answer::Uint(n)
+        } else if let Ok(n) = text.parse::<i64>() {
+            Answer::Int(n)
+        } else {
+            Answer::Str(String::from(text))
+        }
+    }
+
+    /// The value of a numeric answer.
+    pub fn as_number(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n as i128),
+            Answer::Uint(n) => Some(*n as i128),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Uint(n) => write!(f, "{}", n),
+            Answer::Str(text) => write!(f, "{}", text),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self.as_number(), other.as_number()) {
+            (Some(n), Some(m)) => n == m,
+            (None, None) => self.to_string() == other.to_string(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Answer) -> Option<Ordering> {
+        match (self.as_number(), other.as_number()) {
+            (Some(n), Some(m)) => Some(n.cmp(&m)),
+            _ => (self == other).then_some(Ordering::Equal),
+        }
+    }
+}
+
+macro_rules! from_number {
+    ($variant:ident as $inner:ty: $($number:ty),*) => {
+        $(impl From<$number> for Answer {
+            fn from(n: $number) -> Answer {
+                Answer::$variant(n as $inner)
+            }
+        })*
+    };
+}
+
+from_number!(Int as i64: i8, i16, i32, i64, isize);
+from_number!(Uint as u64: u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Str(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Str(String::from(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("7", Answer::Uint(7))]
+    #[case("-7", Answer::Int(-7))]
+    #[case("CMZ", Answer::Str(String::from("CMZ")))]
+    #[case("#..#\n####", Answer::Lines(vec![String::from("#..#"), String::from("####")]))]
+    fn test_parse(#[case] text: &str, #[case] expected: Answer) {
+        let answer = Answer::parse(text);
+        assert!(matches!(
+            (&answer, &expected),
+            (Answer::Int(_), Answer::Int(_))
+                | (Answer::Uint(_), Answer::Uint(_))
+                | (Answer::Str(_), Answer::Str(_))
+                | (Answer::Lines(_), Answer::Lines(_))
+        ));
+        assert_eq!(answer, expected);
+        assert_eq!(answer.to_string(), text);
+    }
+
+    #[rstest]
+    #[case(Answer::from(7), Answer::from(7u64), true)]
+    #[case(Answer::from(-1), Answer::from(u64::MAX), false)]
+    #[case(Answer::from("7"), Answer::from(7), false)]
+    #[case(Answer::from("CMZ"), Answer::Lines(vec![String::from("CMZ")]), true)]
+    fn test_eq(#[case] answer: Answer, #[case] other: Answer, #[case] expected: bool) {
+        assert_eq!(answer == other, expected);
+    }
+
+    #[rstest]
+    #[case(Answer::from(9), Answer::from(10u32), Some(Ordering::Less))]
+    #[case(Answer::from(-3), Answer::from(-20), Some(Ordering::Greater))]
+    #[case(Answer::from("CMZ"), Answer::from("CMZ"), Some(Ordering::Equal))]
+    #[case(Answer::from("CMZ"), Answer::from("MCD"), None)]
+    #[case(Answer::from("9"), Answer::from(10), None)]
+    fn test_partial_cmp(
+        #[case] answer: Answer,
+        #[case] other: Answer,
+        #[case] expected: Option<Ordering>,
+    ) {
+        assert_eq!(answer.partial_cmp(&other), expected);
+    }
+}
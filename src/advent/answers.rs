@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{AdventError, Answer, Solution};
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// The accepted answers of each challenge. They are stored as TOML with one
+/// table per day, e.g. a `[2022.5]` table holding `part1` and `part2`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<(u32, u32), DayAnswers>,
+}
+
+impl Answers {
+    /// Loads the answers at `path`, where a missing file has no answers.
+    pub fn load(path: &Path) -> Result<Answers, AdventError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(AdventError::Io {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, AdventError> {
+        let years = toml::from_str::<BTreeMap<String, BTreeMap<String, DayAnswers>>>(text)
+            .map_err(|err| {
+                AdventError::parse_at(text, err.span().map_or(0, |span| span.start), err.message())
+            })?;
+        let parse_key = |key: &str| {
+            key.parse::<u32>().map_err(|_| {
+                AdventError::parse_at(
+                    text,
+                    text.find(key).unwrap_or(0),
+                    "answers should be keyed by year and day",
+                )
+            })
+        };
+        let mut days = BTreeMap::new();
+        for (year, answers_of_year) in years {
+            for (day, answers) in answers_of_year {
+                days.insert((parse_key(&year)?, parse_key(&day)?), answers);
+            }
+        }
+        Ok(Answers { days })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AdventError> {
+        fs::write(path, self.to_toml()).map_err(|err| AdventError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })
+    }
+
+    pub fn to_toml(&self) -> String {
+        self.days
+            .iter()
+            .map(|((year, day), answers)| {
+                let mut table = format!("[{}.{}]\n", year, day);
+                [("part1", &answers.part1), ("part2", &answers.part2)]
+                    .into_iter()
+                    .filter_map(|(key, answer)| Some((key, answer.as_ref()?)))
+                    .for_each(|(key, answer)| {
+                        let _ = writeln!(table, "{} = {}", key, toml::Value::from(answer.as_str()));
+                    });
+                table
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// The year and day of every challenge with answers.
+    pub fn days(&self) -> Vec<(u32, u32)> {
+        self.days.keys().copied().collect::<Vec<(u32, u32)>>()
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u8) -> Option<&str> {
+        let answers = self.days.get(&(year, day))?;
+        match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Stores the answers of every part the solution solved, keeping the
+    /// existing answers of parts that were not run or failed.
+    pub fn record(&mut self, solution: &Solution) {
+        let answers = self.days.entry((solution.year, solution.day)).or_default();
+        if let Some(Ok(answer)) = &solution.part1 {
+            answers.part1 = Some(answer.to_string());
+        }
+        if let Some(Ok(answer)) = &solution.part2 {
+            answers.part2 = Some(answer.to_string());
+        }
+    }
+
+    /// Checks every part the solutions ran against the accepted answers.
+    pub fn verify(&self, solutions: &[Solution]) -> Vec<Check> {
+        solutions
+            .iter()
+            .flat_map(|solution| {
+                [(1, &solution.part1), (2, &solution.part2)]
+                    .into_iter()
+                    .filter_map(move |(part, actual)| {
+                        let actual = actual.clone()?;
+                        let expected = self
+                            .get(solution.year, solution.day, part)
+                            .map(Answer::parse);
+                        let verdict = match (&expected, &actual) {
+                            (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass,
+                            (None, Ok(_)) => Verdict::Missing,
+                            _ => Verdict::Fail,
+                        };
+                        Some(Check {
+                            year: solution.year,
+                            day: solution.day,
+                            part,
+                            verdict,
+                            expected,
+                            actual,
+                        })
+                    })
+            })
+            .collect::<Vec<Check>>()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+/// The outcome of verifying one part of a challenge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub expected: Option<Answer>,
+    pub actual: Result<Answer, AdventError>,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.verdict {
+            Verdict::Pass => "PASS",
+            Verdict::Fail => "FAIL",
+            Verdict::Missing => "MISSING",
+        };
+        write!(
+            f,
+            "{:<8}{} day {:>2} part {}",
+            label, self.year, self.day, self.part
+        )?;
+        match (&self.expected, &self.actual) {
+            (Some(expected), Ok(actual)) if self.verdict == Verdict::Fail => {
+                write!(f, ": expected {}, got {}", expected, actual)
+            }
+            (_, Ok(actual)) => write!(f, ": {}", actual),
+            (_, Err(err)) => write!(f, ": {}", err),
+        }
+    }
+}
+
+/// How many checks passed, failed and had no accepted answer.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tally {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Tally {
+    pub fn of(checks: &[Check]) -> Tally {
+        let count = |verdict| {
+            checks
+                .iter()
+                .filter(|check| check.verdict == verdict)
+                .count()
+        };
+        Tally {
+            passed: count(Verdict::Pass),
+            failed: count(Verdict::Fail),
+            missing: count(Verdict::Missing),
+        }
+    }
+}
+
+impl fmt::Display for Tally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} missing",
+            self.passed, self.failed, self.missing
+        )
+    }
+}
+
+/// Checks the solutions against the accepted answers in the file at `path`.
+pub fn verify_answers(path: &Path, solutions: &[Solution]) -> Result<Vec<Check>, AdventError> {
+    Ok(Answers::load(path)?.verify(solutions))
+}
+
+/// Stores the answers of the solutions in the file at `path`, keeping the
+/// accepted answers of every part they didn't solve.
+pub fn record_answers(path: &Path, solutions: &[Solution]) -> Result<(), AdventError> {
+    let mut answers = Answers::load(path)?;
+    solutions
+        .iter()
+        .for_each(|solution| answers.record(solution));
+    answers.save(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::Timing;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    const ANSWERS: &str = "[2022.5]\npart1 = \"CMZ\"\npart2 = \"MCD\"\n\n[2022.6]\npart1 = \"7\"\n";
+
+    fn solution(day: u32, part1: &str, part2: &str) -> Solution {
+        Solution {
+            year: 2022,
+            day,
+            title: "",
+            part1: Some(Ok(Answer::parse(part1))),
+            part2: Some(Ok(Answer::parse(part2))),
+            timing: Timing::default(),
+        }
+    }
+
+    #[rstest]
+    #[case(5, 1, Some("CMZ"))]
+    #[case(5, 2, Some("MCD"))]
+    #[case(6, 2, None)]
+    #[case(7, 1, None)]
+    fn test_get(#[case] day: u32, #[case] part: u8, #[case] expected: Option<&str>) {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(2022, day, part), expected);
+    }
+
+    #[rstest]
+    #[case("[2022.5]\npart3 = \"CMZ\"\n", 2)]
+    #[case("[2022.five]\npart1 = \"CMZ\"\n", 1)]
+    fn test_parse_error(#[case] text: &str, #[case] line: usize) {
+        assert!(matches!(
+            Answers::parse(text),
+            Err(AdventError::Parse { line: l, .. }) if l == line
+        ));
+    }
+
+    #[rstest]
+    fn test_round_trip() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.to_toml(), ANSWERS);
+    }
+
+    #[rstest]
+    #[case(solution(5, "CMZ", "MCD"), [Verdict::Pass, Verdict::Pass])]
+    #[case(solution(5, "CMZ", "XYZ"), [Verdict::Pass, Verdict::Fail])]
+    #[case(solution(6, "7", "19"), [Verdict::Pass, Verdict::Missing])]
+    fn test_verify(#[case] solution: Solution, #[case] expected: [Verdict; 2]) {
+        let answers = Answers::parse(ANSWERS).unwrap();
+        let verdicts = answers
+            .verify(&[solution])
+            .into_iter()
+            .map(|check| check.verdict)
+            .collect::<Vec<Verdict>>();
+        assert_eq!(verdicts, expected);
+    }
+
+    #[rstest]
+    fn test_record() {
+        let mut answers = Answers::parse(ANSWERS).unwrap();
+        answers.record(&solution(6, "7", "19"));
+        assert_eq!(answers.get(2022, 6, 2), Some("19"));
+    }
+
+    #[rstest]
+    fn test_record_then_verify_answers() {
+        let path = env::temp_dir().join(format!("rusty_advent_answers_{}.toml", process::id()));
+        fs::write(&path, ANSWERS).unwrap();
+        record_answers(&path, &[solution(6, "7", "19")]).unwrap();
+        let checks =
+            verify_answers(&path, &[solution(5, "CMZ", "XYZ"), solution(6, "7", "19")]).unwrap();
+        assert_eq!(
+            Tally::of(&checks),
+            Tally {
+                passed: 3,
+                failed: 1,
+                missing: 0
+            }
+        );
+        assert_eq!(
+            Tally::of(&checks).to_string(),
+            "3 passed, 1 failed, 0 missing"
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt::{self, Write};
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::isolate::isolate;
+use super::timing::{fmt_duration, time};
+use super::{solvers, AdventError, Input, Part, Solver};
+
+/// A benchmarked step of a challenge.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Parse => f.pad("parse"),
+            Stage::Part1 => f.pad("part 1"),
+            Stage::Part2 => f.pad("part 2"),
+        }
+    }
+}
+
+/// Summary statistics of the timed runs of a stage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// The sample standard deviation, which is zero for a single run.
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// The statistics of the samples, or `None` if there are none.
+    pub fn of(samples: &[Duration]) -> Option<Stats> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let runs = sorted.len();
+        let min = *sorted.first()?;
+        let median = match runs % 2 {
+            0 => (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2,
+            _ => sorted[runs / 2],
+        };
+        let mean = sorted.iter().sum::<Duration>() / runs as u32;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / (runs.max(2) - 1) as f64;
+        Some(Stats {
+            runs,
+            min,
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// The benchmark of every selected stage of one challenge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bench {
+    pub year: u32,
+    pub day: u32,
+    pub title: &'static str,
+    pub stages: Vec<(Stage, Result<Stats, AdventError>)>,
+}
+
+/// Benchmarks the solvers of the given year and day, where 0 selects every
+/// year or day, one after another so that they don't compete for the CPU.
+/// Each stage runs `warmup` times untimed and then `runs` times timed. The
+/// parse step is benchmarked too for solvers that have one.
+pub fn bench_challenges(
+    year: u32,
+    day: u32,
+    input: &Input,
+    part: Part,
+    warmup: usize,
+    runs: usize,
+) -> Vec<Bench> {
+    solvers()
+        .into_iter()
+        .filter(|solver| (year == 0 || solver.year() == year) && (day == 0 || solver.day() == day))
+        .map(|solver| bench(solver, input, part, warmup, runs))
+        .collect::<Vec<Bench>>()
+}
+
+fn bench(solver: &dyn Solver, input: &Input, part: Part, warmup: usize, runs: usize) -> Bench {
+    let input = input.read(solver.year(), solver.day());
+    let mut stages = Vec::new();
+    if let Ok(input) = &input {
+        if let Ok(Some(_)) = isolate(|| solver.parse(input)) {
+            stages.push((
+                Stage::Parse,
+                bench_stage(warmup, runs, input, |input| {
+                    solver.parse(input).unwrap_or(Ok(()))
+                }),
+            ));
+        }
+    }
+    [
+        (Stage::Part1, part.includes_part_1()),
+        (Stage::Part2, part.includes_part_2()),
+    ]
+    .into_iter()
+    .filter(|(_, selected)| *selected)
+    .for_each(|(stage, _)| {
+        let stats = match &input {
+            Ok(input) => bench_stage(warmup, runs, input, |input| match stage {
+                Stage::Part1 => solver.part_1(input),
+                _ => solver.part_2(input),
+            }),
+            Err(err) => Err(err.clone()),
+        };
+        stages.push((stage, stats));
+    });
+    Bench {
+        year: solver.year(),
+        day: solver.day(),
+        title: solver.title(),
+        stages,
+    }
+}
+
+/// Runs a stage on `input` `warmup` times and then times it `runs` times,
+/// stopping at the first failure. The input and result go through
+/// `black_box` so that the optimizer can't hoist or drop the work.
+fn bench_stage<T, F>(
+    warmup: usize,
+    runs: usize,
+    input: &str,
+    stage: F,
+) -> Result<Stats, AdventError>
+where
+    F: Fn(&str) -> Result<T, AdventError>,
+{
+    let run = || black_box(isolate(|| stage(black_box(input))));
+    for _ in 0..warmup {
+        run()??;
+    }
+    let samples = (0..runs.max(1))
+        .map(|_| match time(run) {
+            (Ok(Ok(_)), duration) => Ok(duration),
+            (Ok(Err(err)) | Err(err), _) => Err(err),
+        })
+        .collect::<Result<Vec<Duration>, AdventError>>()?;
+    Stats::of(&samples).ok_or_else(|| AdventError::no_solution("no runs to time"))
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+struct BaselineEntry {
+    year: u32,
+    day: u32,
+    stage: Stage,
+    median_ns: u64,
+    mean_ns: u64,
+}
+
+/// Saved benchmark medians and means to compare later benchmarks against,
+/// stored as TOML with one `[[bench]]` table per stage.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Baseline {
+    #[serde(default, rename = "bench")]
+    entries: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    /// The baseline of every stage that was benchmarked successfully.
+    pub fn of(benches: &[Bench]) -> Baseline {
+        Baseline {
+            entries: benches
+                .iter()
+                .flat_map(|bench| {
+                    bench.stages.iter().filter_map(|(stage, stats)| {
+                        let stats = stats.as_ref().ok()?;
+                        Some(BaselineEntry {
+                            year: bench.year,
+                            day: bench.day,
+                            stage: *stage,
+                            median_ns: stats.median.as_nanos() as u64,
+                            mean_ns: stats.mean.as_nanos() as u64,
+                        })
+                    })
+                })
+                .collect::<Vec<BaselineEntry>>(),
+        }
+    }
+
+    /// Loads the baseline at `path`, or `None` if there is no such file.
+    pub fn load(path: &Path) -> Result<Option<Baseline>, AdventError> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str::<Baseline>(&text).map(Some).map_err(|err| {
+                AdventError::parse_at(
+                    &text,
+                    err.span().map_or(0, |span| span.start),
+                    err.message(),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(AdventError::Io {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AdventError> {
+        let io_error = |reason: String| AdventError::Io {
+            path: path.display().to_string(),
+            reason,
+        };
+        let text = toml::to_string(self).map_err(|err| io_error(err.to_string()))?;
+        fs::write(path, text).map_err(|err| io_error(err.to_string()))
+    }
+
+    /// The saved median of a stage.
+    fn median(&self, year: u32, day: u32, stage: Stage) -> Option<Duration> {
+        self.entries
+            .iter()
+            .find(|entry| entry.year == year && entry.day == day && entry.stage == stage)
+            .map(|entry| Duration::from_nanos(entry.median_ns))
+    }
+}
+
+/// Tabulates the statistics of every stage. With a baseline, the change of
+/// each median from the baseline's is shown too.
+pub fn bench_report(benches: &[Bench], baseline: Option<&Baseline>) -> String {
+    let mut report = format!(
+        "{:<6}{:>4}  {:<8}{:>6}{:>12}{:>12}{:>12}{:>12}",
+        "year", "day", "stage", "runs", "min", "median", "mean", "stddev"
+    );
+    if baseline.is_some() {
+        let _ = write!(report, "{:>12}", "baseline");
+    }
+    report.push('\n');
+    for bench in benches {
+        for (stage, stats) in &bench.stages {
+            let _ = write!(report, "{:<6}{:>4}  {:<8}", bench.year, bench.day, stage);
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(err) => {
+                    let _ = writeln!(report, "  FAILED: {}", err);
+                    continue;
+                }
+            };
+            let _ = write!(
+                report,
+                "{:>6}{:>12}{:>12}{:>12}{:>12}",
+                stats.runs,
+                fmt_duration(Some(stats.min)),
+                fmt_duration(Some(stats.median)),
+                fmt_duration(Some(stats.mean)),
+                fmt_duration(Some(stats.stddev)),
+            );
+            if let Some(baseline) = baseline {
+                let change = baseline
+                    .median(bench.year, bench.day, *stage)
+                    .filter(|before| !before.is_zero())
+                    .map_or_else(
+                        || String::from("-"),
+                        |before| {
+                            let change =
+                                stats.median.as_secs_f64() / before.as_secs_f64() * 100.0 - 100.0;
+                            format!("{:+.1}%", change)
+                        },
+                    );
+                let _ = write!(report, "{:>12}", change);
+            }
+            report.push('\n');
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::Answer;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples
+            .iter()
+            .map(|millis| Duration::from_millis(*millis))
+            .collect::<Vec<Duration>>()
+    }
+
+    fn bench(part1: Result<Stats, AdventError>) -> Bench {
+        Bench {
+            year: 2022,
+            day: 5,
+            title: "Supply Stacks",
+            stages: vec![
+                (Stage::Parse, Ok(Stats::of(&millis(&[1])).unwrap())),
+                (Stage::Part1, part1),
+            ],
+        }
+    }
+
+    #[rstest]
+    #[case(&[], None)]
+    #[case(&[3], Some((3, 3, 3, 0)))]
+    #[case(&[4, 1, 3], Some((1, 3, 2, 1)))]
+    #[case(&[2, 4, 4, 4, 5, 5, 7, 9], Some((2, 4, 5, 2)))]
+    fn test_stats(#[case] samples: &[u64], #[case] expected: Option<(u64, u64, u64, u64)>) {
+        let stats = Stats::of(&millis(samples)).map(|stats| {
+            (
+                stats.min.as_millis() as u64,
+                stats.median.as_millis() as u64,
+                stats.mean.as_millis() as u64,
+                stats.stddev.as_millis() as u64,
+            )
+        });
+        assert_eq!(stats, expected);
+    }
+
+    #[rstest]
+    fn test_bench_stage() {
+        let stats = bench_stage(2, 5, "7", |input| Ok(Answer::from(input))).unwrap();
+        assert_eq!(stats.runs, 5);
+        let empty = |_: &str| Err::<Answer, _>(AdventError::no_solution("empty"));
+        assert_eq!(
+            bench_stage(2, 5, "", empty),
+            Err(AdventError::no_solution("empty"))
+        );
+    }
+
+    #[rstest]
+    fn test_bench_report() {
+        let benches = [bench(Ok(Stats::of(&millis(&[2, 4])).unwrap()))];
+        let mut baseline = Baseline::of(&[bench(Ok(Stats::of(&millis(&[2])).unwrap()))]);
+        baseline.entries.retain(|entry| entry.stage == Stage::Part1);
+        assert_eq!(
+            bench_report(&benches, Some(&baseline)),
+            "year   day  stage     runs         min      median        mean      stddev    baseline\n\
+             2022     5  parse        1      1.00ms      1.00ms      1.00ms      0.00ns           -\n\
+             2022     5  part 1       2      2.00ms      3.00ms      3.00ms      1.41ms      +50.0%\n"
+        );
+        let benches = [bench(Err(AdventError::no_solution("empty")))];
+        assert!(bench_report(&benches, None).ends_with("  part 1    FAILED: no solution: empty\n"));
+    }
+
+    #[rstest]
+    fn test_baseline_round_trip() {
+        let path = env::temp_dir().join(format!("rusty_advent_bench_{}.toml", process::id()));
+        let _ = fs::remove_file(&path);
+        assert_eq!(Baseline::load(&path), Ok(None));
+        let baseline = Baseline::of(&[bench(Err(AdventError::no_solution("empty")))]);
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path), Ok(Some(baseline)));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Instant;
+
+use super::AdventError;
+
+thread_local! {
+    static TOKEN: RefCell<CancelToken> = RefCell::new(CancelToken::default());
+}
+
+/// Tells a solver that the runner gave up on it, so it can stop early
+/// instead of running on in the background.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fails once cancelled, for solvers to poll with `?`.
+    pub fn check(&self) -> Result<(), AdventError> {
+        if self.is_cancelled() {
+            Err(AdventError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The cancel token of the challenge running on this thread, which is only
+/// ever cancelled when the run has a timeout.
+pub fn cancel_token() -> CancelToken {
+    TOKEN.with(|token| token.borrow().clone())
+}
+
+/// Runs `task` on a thread of its own, with `token` as its cancel token, and
+/// waits for it until `deadline`. If the deadline passes first, the token is
+/// cancelled and the thread is left to wind down on its own.
+pub fn with_deadline<R, F>(deadline: Instant, token: &CancelToken, task: F) -> Option<R>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let Some(remaining) = deadline
+        .checked_duration_since(Instant::now())
+        .filter(|remaining| !remaining.is_zero())
+    else {
+        token.cancel();
+        return None;
+    };
+    let (sender, receiver) = mpsc::channel();
+    let worker_token = token.clone();
+    thread::spawn(move || {
+        TOKEN.with(|token| *token.borrow_mut() = worker_token);
+        let _ = sender.send(task());
+    });
+    match receiver.recv_timeout(remaining) {
+        Ok(result) => Some(result),
+        Err(_) => {
+            token.cancel();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    fn test_with_deadline() {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let token = CancelToken::default();
+        assert_eq!(with_deadline(deadline, &token, || 7), Some(7));
+        assert!(!token.is_cancelled());
+    }
+
+    #[rstest]
+    fn test_with_deadline_cancels() {
+        let deadline = Instant::now() + Duration::from_millis(20);
+        let token = CancelToken::default();
+        let (sender, receiver) = mpsc::channel();
+        let result = with_deadline(deadline, &token, move || {
+            while cancel_token().check().is_ok() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            let _ = sender.send(cancel_token().check());
+        });
+        assert_eq!(result, None);
+        assert!(token.is_cancelled());
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(5)),
+            Ok(Err(AdventError::Cancelled))
+        );
+        assert!(!cancel_token().is_cancelled());
+    }
+}
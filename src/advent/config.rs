@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Deserializer};
+
+use super::{AdventError, Format};
+
+/// The name of the project config, looked up from the working directory
+/// upward.
+pub const CONFIG_FILE: &str = ".rusty_advent.toml";
+
+/// Project defaults from a `.rusty_advent.toml`, e.g.
+///
+/// ```toml
+/// inputs-dir = "../inputs"
+/// year = 2022
+/// format = "text"
+/// session-file = ".session"
+/// jobs = 0
+/// answers = "answers.toml"
+/// history = "history.toml"
+/// ```
+///
+/// Every setting is optional. Relative paths are relative to the config
+/// file rather than to the working directory.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub inputs_dir: Option<PathBuf>,
+    pub year: Option<u32>,
+    #[serde(default, deserialize_with = "format")]
+    pub format: Option<Format>,
+    pub session_file: Option<PathBuf>,
+    pub jobs: Option<usize>,
+    pub answers: Option<PathBuf>,
+    pub history: Option<PathBuf>,
+}
+
+impl Config {
+    /// Finds the closest config in `dir` or one of its ancestors and loads
+    /// it, returning `None` if there is none.
+    pub fn discover(dir: &Path) -> Result<Option<Config>, AdventError> {
+        dir.ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+            .map(|path| Config::load(&path))
+            .transpose()
+    }
+
+    pub fn load(path: &Path) -> Result<Config, AdventError> {
+        let text = fs::read_to_string(path).map_err(|err| AdventError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        let config = Config::parse(&text)?;
+        Ok(config.relative_to(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    pub fn parse(text: &str) -> Result<Config, AdventError> {
+        toml::from_str::<Config>(text).map_err(|err| {
+            AdventError::parse_at(text, err.span().map_or(0, |span| span.start), err.message())
+        })
+    }
+
+    /// Resolves the relative paths of the config against `dir`.
+    fn relative_to(self, dir: &Path) -> Config {
+        let resolve = |path: Option<PathBuf>| path.map(|path| dir.join(path));
+        Config {
+            inputs_dir: resolve(self.inputs_dir),
+            session_file: resolve(self.session_file),
+            answers: resolve(self.answers),
+            history: resolve(self.history),
+            ..self
+        }
+    }
+}
+
+fn format<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Format>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|format| format.parse::<Format>().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    #[rstest]
+    #[case("", Config::default())]
+    #[case(
+        "year = 2022\nformat = \"json\"\njobs = 0\n",
+        Config {
+            year: Some(2022),
+            format: Some(Format::Json),
+            jobs: Some(0),
+            ..Config::default()
+        }
+    )]
+    fn test_parse(#[case] text: &str, #[case] expected: Config) {
+        assert_eq!(Config::parse(text), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("year = 2022\nformat = \"yaml\"\n", 2)]
+    #[case("year = 2022\ninputs = \"inputs\"\n", 2)]
+    fn test_parse_error(#[case] text: &str, #[case] line: usize) {
+        assert!(matches!(
+            Config::parse(text),
+            Err(AdventError::Parse { line: l, .. }) if l == line
+        ));
+    }
+
+    #[rstest]
+    fn test_discover() {
+        let root = env::temp_dir().join(format!("rusty_advent_config_{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let nested = root.join("src").join("advent");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            root.join(CONFIG_FILE),
+            "inputs-dir = \"../inputs\"\nanswers = \"answers.toml\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Config::discover(&nested),
+            Ok(Some(Config {
+                inputs_dir: Some(root.join("../inputs")),
+                answers: Some(root.join("answers.toml")),
+                ..Config::default()
+            }))
+        );
+        fs::remove_file(root.join(CONFIG_FILE)).unwrap();
+        assert_eq!(Config::discover(&nested), Ok(None));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
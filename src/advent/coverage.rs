@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::fs;
+use std::path::Path;
+
+use super::{solvers, Answers};
+
+/// The rows shown for each year, with the most a year can have of each.
+const ROWS: [(&str, usize); 4] = [
+    ("solvers", 25),
+    ("inputs", 25),
+    ("stars", 50),
+    ("tests", 25),
+];
+
+/// How far along a single day is.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct DayCoverage {
+    solver: bool,
+    input: bool,
+    /// How many parts have an accepted answer.
+    stars: u8,
+    tests: bool,
+}
+
+impl DayCoverage {
+    /// The day's mark in a row of the grid, along with what it counts for.
+    fn mark(&self, row: &str) -> (&'static str, usize) {
+        let present = match row {
+            "solvers" => self.solver,
+            "inputs" => self.input,
+            "tests" => self.tests,
+            _ => {
+                return match self.stars {
+                    0 => (".", 0),
+                    1 => ("*", 1),
+                    _ => ("**", 2),
+                }
+            }
+        };
+        if present {
+            ("#", 1)
+        } else {
+            (".", 0)
+        }
+    }
+}
+
+/// Which days have a solver, an input, accepted answers and tests, for every
+/// year that has any of them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Coverage {
+    days: BTreeMap<(u32, u32), DayCoverage>,
+}
+
+impl Coverage {
+    /// Collects the coverage of the registered solvers, the inputs in
+    /// `inputs_dir` and the accepted answers. Whether a day has tests is read
+    /// from its source under `root`.
+    pub fn collect(root: &Path, inputs_dir: &Path, answers: &Answers) -> Coverage {
+        let mut days = BTreeMap::<(u32, u32), DayCoverage>::new();
+        for solver in solvers() {
+            let source = root
+                .join("src")
+                .join("advent")
+                .join(format!("advent_{}", solver.year()))
+                .join(format!("day_{:02}.rs", solver.day()));
+            let day = days.entry((solver.year(), solver.day())).or_default();
+            day.solver = true;
+            day.tests = fs::read_to_string(source).is_ok_and(|source| source.contains("mod tests"));
+        }
+        fs::read_dir(inputs_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.metadata().is_ok_and(|metadata| metadata.len() > 0))
+            .filter_map(|entry| parse_input_file_name(&entry.file_name().to_string_lossy()))
+            .for_each(|key| days.entry(key).or_default().input = true);
+        for (year, day) in answers.days() {
+            days.entry((year, day)).or_default().stars = (1..=2)
+                .filter(|part| answers.get(year, day, *part).is_some())
+                .count() as u8;
+        }
+        Coverage { days }
+    }
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut years = self
+            .days
+            .keys()
+            .map(|(year, _)| *year)
+            .collect::<Vec<u32>>();
+        years.dedup();
+        let mut grid = String::new();
+        for (idx, year) in years.iter().enumerate() {
+            if idx > 0 {
+                grid.push('\n');
+            }
+            let _ = write!(grid, "{:<10}", year);
+            (1..=25).for_each(|day| {
+                let _ = write!(grid, "{:>3}", day);
+            });
+            grid.push('\n');
+            for (row, most) in ROWS {
+                let marks = (1..=25)
+                    .map(|day| {
+                        let day = self.days.get(&(*year, day)).copied().unwrap_or_default();
+                        day.mark(row)
+                    })
+                    .collect::<Vec<(&str, usize)>>();
+                let _ = write!(grid, "  {:<8}", row);
+                marks.iter().for_each(|(mark, _)| {
+                    let _ = write!(grid, "{:>3}", mark);
+                });
+                let count = marks.iter().map(|(_, count)| count).sum::<usize>();
+                let _ = writeln!(grid, "  {:>2}/{}", count, most);
+            }
+        }
+        write!(f, "{}", grid)
+    }
+}
+
+/// The year and day of an input named like `y2022d05.txt`.
+fn parse_input_file_name(name: &str) -> Option<(u32, u32)> {
+    let (year, day) = name
+        .strip_prefix('y')?
+        .strip_suffix(".txt")?
+        .split_once('d')?;
+    Some((year.parse::<u32>().ok()?, day.parse::<u32>().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("y2022d05.txt", Some((2022, 5)))]
+    #[case("y2021d12.txt", Some((2021, 12)))]
+    #[case("y2022d05.txt.bak", None)]
+    #[case("notes.txt", None)]
+    fn test_parse_input_file_name(#[case] name: &str, #[case] expected: Option<(u32, u32)>) {
+        assert_eq!(parse_input_file_name(name), expected);
+    }
+
+    #[rstest]
+    fn test_display() {
+        let mut days = BTreeMap::new();
+        days.insert(
+            (2022, 1),
+            DayCoverage {
+                solver: true,
+                input: true,
+                stars: 2,
+                tests: true,
+            },
+        );
+        days.insert(
+            (2022, 3),
+            DayCoverage {
+                input: true,
+                stars: 1,
+                ..DayCoverage::default()
+            },
+        );
+        let mut expected = String::from("2022      ");
+        (1..=25).for_each(|day| expected.push_str(&format!("{:>3}", day)));
+        expected.push('\n');
+        let rest = "  .".repeat(22);
+        expected.push_str(&format!("  solvers   #  .  .{}   1/25\n", rest));
+        expected.push_str(&format!("  inputs    #  .  #{}   2/25\n", rest));
+        expected.push_str(&format!("  stars    **  .  *{}   3/50\n", rest));
+        expected.push_str(&format!("  tests     #  .  .{}   1/25\n", rest));
+        assert_eq!(Coverage { days }.to_string(), expected);
+    }
+}
@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// Everything that can go wrong while solving a challenge. Solvers return
+/// these instead of panicking so that one bad input doesn't abort a full run,
+/// and a solver that panics anyway is reported as `Panic`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AdventError {
+    /// The puzzle input could not be read from `path`.
+    MissingInput { path: String, reason: String },
+    /// The puzzle input is malformed at the given (1-based) line and column.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        reason: String,
+    },
+    /// The puzzle input is well-formed but has no answer.
+    NoSolution(String),
+    /// A file other than the puzzle input could not be read or written.
+    Io { path: String, reason: String },
+    /// The solver panicked, with the panic message.
+    Panic(String),
+    /// The challenge didn't finish within the run's timeout.
+    Timeout(Duration),
+    /// The solver stopped because its cancel token was cancelled.
+    Cancelled,
+}
+
+impl AdventError {
+    /// Builds a parse error for `text` found at the byte `offset` (0-based)
+    /// of the `line_idx`-th (0-based) line of the input.
+    pub fn parse(line_idx: usize, offset: usize, text: &str, reason: &str) -> AdventError {
+        AdventError::Parse {
+            line: line_idx + 1,
+            column: offset + 1,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Builds a parse error for the line of `input` containing the byte
+    /// `offset`, for errors that only know where they happened in the input.
+    pub fn parse_at(input: &str, offset: usize, reason: &str) -> AdventError {
+        let offset = offset.min(input.len());
+        let line_idx = input[..offset].matches('\n').count();
+        let start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line = input[start..].lines().next().unwrap_or("");
+        AdventError::Parse {
+            line: line_idx + 1,
+            column: offset - start + 1,
+            text: line.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn no_solution(reason: &str) -> AdventError {
+        AdventError::NoSolution(reason.to_string())
+    }
+}
+
+impl fmt::Display for AdventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdventError::MissingInput { path, reason } => {
+                write!(f, "missing input {}: {}", path, reason)
+            }
+            AdventError::Parse {
+                line,
+                column,
+                text,
+                reason,
+            } => write!(
+                f,
+                "parse error at line {}, column {} on {:?}: {}",
+                line, column, text, reason
+            ),
+            AdventError::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            AdventError::Io { path, reason } => write!(f, "could not access {}: {}", path, reason),
+            AdventError::Panic(message) => write!(f, "panicked: {}", message),
+            AdventError::Timeout(timeout) => write!(f, "timed out after {:?}", timeout),
+            AdventError::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl Error for AdventError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0, 1, 1)]
+    #[case(4, 12, 5, 13)]
+    fn test_parse(
+        #[case] line_idx: usize,
+        #[case] offset: usize,
+        #[case] line: usize,
+        #[case] column: usize,
+    ) {
+        assert!(matches!(
+            AdventError::parse(line_idx, offset, "2", ""),
+            AdventError::Parse { line: l, column: c, .. } if l == line && c == column
+        ));
+    }
+
+    #[rstest]
+    #[case("[2022.1]\npart1 = 3", 16, 2, 8, "part1 = 3")]
+    #[case("[2022.1]\npart1 = 3", 0, 1, 1, "[2022.1]")]
+    #[case("[2022.1]\n", 9, 2, 1, "")]
+    fn test_parse_at(
+        #[case] input: &str,
+        #[case] offset: usize,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] text: &str,
+    ) {
+        assert!(matches!(
+            AdventError::parse_at(input, offset, ""),
+            AdventError::Parse { line: l, column: c, text: t, .. }
+                if l == line && c == column && t == text
+        ));
+    }
+}
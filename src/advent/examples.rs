@@ -0,0 +1,268 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::AdventError;
+
+/// An example input from a puzzle page, with the answers the page gives for
+/// it. A part's answer is `None` when the page doesn't solve it with this
+/// example.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[cfg(test)]
+#[derive(Clone, Debug, Default, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct ExampleAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Extracts the examples of a saved puzzle page. Every `<pre><code>` block
+/// introduced by a paragraph mentioning an example counts as one, and the
+/// answer of each part is the last emphasized code in its `<article>`,
+/// given to the example shown most recently before it. When no block is
+/// introduced as an example, the first block is taken as the example.
+pub fn extract_examples(page: &str) -> Vec<Example> {
+    let blocks = find_all(page, "<pre><code>", "</code></pre>");
+    let mut starts = blocks
+        .iter()
+        .filter(|(start, _)| introduces_example(&page[..*start]))
+        .map(|(start, _)| *start)
+        .collect::<Vec<usize>>();
+    if starts.is_empty() {
+        starts.extend(blocks.first().map(|(start, _)| *start));
+    }
+    let mut examples = starts
+        .iter()
+        .enumerate()
+        .map(|(idx, start)| Example {
+            name: format!("example_{}", idx + 1),
+            input: blocks
+                .iter()
+                .find(|(block_start, _)| block_start == start)
+                .map(|(_, text)| unescape(&strip_tags(text)))
+                .unwrap_or_default(),
+            part1: None,
+            part2: None,
+        })
+        .collect::<Vec<Example>>();
+    let articles = match find_all(page, "<article", "</article>") {
+        articles if articles.is_empty() => vec![(0, page)],
+        articles => articles,
+    };
+    for (part, (start, article)) in articles.into_iter().take(2).enumerate() {
+        let Some((offset, answer)) = find_all(article, "<code><em>", "</em></code>").pop() else {
+            continue;
+        };
+        let position = start + "<article".len() + offset;
+        let Some(example) = starts
+            .iter()
+            .rposition(|start| *start < position)
+            .or((!examples.is_empty()).then_some(0))
+            .map(|idx| &mut examples[idx])
+        else {
+            continue;
+        };
+        let answer = Some(unescape(&strip_tags(answer)));
+        match part {
+            0 => example.part1 = answer,
+            _ => example.part2 = answer,
+        }
+    }
+    examples
+}
+
+/// Writes each example to `{name}.txt` in `dir`, and their answers to
+/// `answers.toml` with one table per example. Returns the files written.
+pub fn save_examples(dir: &Path, examples: &[Example]) -> Result<Vec<PathBuf>, AdventError> {
+    let io_error = |path: &Path, err: std::io::Error| AdventError::Io {
+        path: path.display().to_string(),
+        reason: err.to_string(),
+    };
+    fs::create_dir_all(dir).map_err(|err| io_error(dir, err))?;
+    let mut written = Vec::new();
+    let mut answers = String::new();
+    for example in examples {
+        let path = dir.join(format!("{}.txt", example.name));
+        fs::write(&path, &example.input).map_err(|err| io_error(&path, err))?;
+        written.push(path);
+        if !answers.is_empty() {
+            answers.push('\n');
+        }
+        let _ = writeln!(answers, "[{}]", example.name);
+        [("part1", &example.part1), ("part2", &example.part2)]
+            .into_iter()
+            .filter_map(|(key, answer)| Some((key, answer.as_ref()?)))
+            .for_each(|(key, answer)| {
+                let _ = writeln!(answers, "{} = {}", key, toml::Value::from(answer.as_str()));
+            });
+    }
+    let path = dir.join("answers.toml");
+    fs::write(&path, answers).map_err(|err| io_error(&path, err))?;
+    written.push(path);
+    Ok(written)
+}
+
+/// Loads a saved example and its answers from `dir`.
+#[cfg(test)]
+pub fn load_example(dir: &Path, name: &str) -> Result<Example, AdventError> {
+    let read = |path: PathBuf| {
+        fs::read_to_string(&path).map_err(|err| AdventError::MissingInput {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })
+    };
+    let input = read(dir.join(format!("{}.txt", name)))?;
+    let text = read(dir.join("answers.toml"))?;
+    let mut answers = toml::from_str::<std::collections::BTreeMap<String, ExampleAnswers>>(&text)
+        .map_err(|err| {
+        AdventError::parse_at(
+            &text,
+            err.span().map_or(0, |span| span.start),
+            err.message(),
+        )
+    })?;
+    let answers = answers.remove(name).unwrap_or_default();
+    Ok(Example {
+        name: String::from(name),
+        input,
+        part1: answers.part1,
+        part2: answers.part2,
+    })
+}
+
+/// Loads an example saved under `examples/` for a test, e.g.
+/// `example(2022, 5, "example_1")`.
+// Only day tests use it, and every year may be disabled.
+#[cfg(test)]
+#[allow(dead_code)]
+pub fn example(year: u32, day: u32, name: &str) -> Example {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join(super::input::input_dir_name(year, day));
+    load_example(&dir, name).unwrap()
+}
+
+/// Every `open ... close` span in `text`, as the offset of its start and
+/// the text between the two.
+fn find_all<'a>(text: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find(open).map(|idx| offset + idx) {
+        let inner = start + open.len();
+        let Some(end) = text[inner..].find(close).map(|idx| inner + idx) else {
+            break;
+        };
+        spans.push((start, &text[inner..end]));
+        offset = end + close.len();
+    }
+    spans
+}
+
+/// Whether the paragraph just before a block says it's an example.
+fn introduces_example(before: &str) -> bool {
+    let paragraph = before
+        .rfind("<p>")
+        .filter(|start| before[*start..].find("<pre>").is_none())
+        .map_or("", |start| &before[start..]);
+    paragraph.to_lowercase().contains("example")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => (),
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    const PAGE: &str = "<main>\n\
+        <article class=\"day-desc\"><h2>--- Day 5: Supply Stacks ---</h2>\n\
+        <p>For example:</p>\n\
+        <pre><code>    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\n</code></pre>\n\
+        <p>In the first step:</p>\n\
+        <pre><code>[<em>D</em>]        \n[N] [C]    \n</code></pre>\n\
+        <p>The top crates are <code><em>C</em></code> and then <code><em>CMZ</em></code>.</p>\n\
+        </article>\n\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\n\
+        <p>Now <code>a &lt; b &amp;&amp; c</code> and the answer is <code><em>MCD</em></code>.</p>\n\
+        </article>\n\
+        </main>";
+
+    #[rstest]
+    fn test_extract() {
+        assert_eq!(
+            extract_examples(PAGE),
+            vec![Example {
+                name: String::from("example_1"),
+                input: String::from(
+                    "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\n"
+                ),
+                part1: Some(String::from("CMZ")),
+                part2: Some(String::from("MCD")),
+            }]
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "<pre><code>1\n2\n</code></pre><p>the sum is <code><em>3</em></code></p>",
+        "1\n2\n",
+        Some("3")
+    )]
+    #[case(
+        "<p>Given <code>x &gt; y</code>:</p><pre><code>a &lt;b&gt;\n</code></pre>",
+        "a <b>\n",
+        None
+    )]
+    fn test_extract_without_example_text(
+        #[case] page: &str,
+        #[case] input: &str,
+        #[case] part1: Option<&str>,
+    ) {
+        let examples = extract_examples(page);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].input, input);
+        assert_eq!(examples[0].part1.as_deref(), part1);
+    }
+
+    #[rstest]
+    fn test_save_and_load() {
+        let dir = env::temp_dir().join(format!("rusty_advent_examples_{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let examples = extract_examples(PAGE);
+        assert_eq!(
+            save_examples(&dir, &examples).map(|paths| paths.len()),
+            Ok(2)
+        );
+        assert_eq!(load_example(&dir, "example_1"), Ok(examples[0].clone()));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
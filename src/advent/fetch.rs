@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::input::input_file_name;
+use super::AdventError;
+
+/// The site puzzle inputs are downloaded from by default.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Downloads puzzle inputs into an inputs directory, waiting at least
+/// `interval` between requests so that the server isn't hammered.
+pub struct Fetcher {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+/// Where a fetched input came from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already in the inputs directory, so nothing was requested.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: Option<String>, interval: Duration) -> Fetcher {
+        Fetcher {
+            agent: agent(),
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session,
+            interval,
+            last_request: None,
+        }
+    }
+
+    /// Saves the input of a challenge as `yYYYYdDD.txt` in `inputs_dir`,
+    /// unless it is already there. Empty files, such as the placeholders made
+    /// when scaffolding a day, don't count as cached.
+    pub fn fetch(
+        &mut self,
+        inputs_dir: &Path,
+        year: u32,
+        day: u32,
+    ) -> Result<Fetched, AdventError> {
+        let path = inputs_dir.join(input_file_name(year, day));
+        if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let session = self.session.as_deref().ok_or_else(|| AdventError::Io {
+            path: url.clone(),
+            reason: String::from("no session token to download with"),
+        })?;
+        if let Some(wait) = self
+            .last_request
+            .and_then(|last| self.interval.checked_sub(last.elapsed()))
+        {
+            thread::sleep(wait);
+        }
+        self.last_request = Some(Instant::now());
+        let input = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .call()
+            .map_err(|err| request_error(&url, err))?
+            .into_string()
+            .map_err(|err| AdventError::Io {
+                path: url.clone(),
+                reason: err.to_string(),
+            })?;
+        fs::create_dir_all(inputs_dir).map_err(|err| AdventError::Io {
+            path: inputs_dir.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        fs::write(&path, input).map_err(|err| AdventError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+/// An HTTP agent that identifies the tool, as the site asks automated
+/// clients to do.
+pub fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .user_agent(concat!(
+            "rusty_advent/",
+            env!("CARGO_PKG_VERSION"),
+            " (+https://github.com/latenitecoding/rusty_advent)"
+        ))
+        .build()
+}
+
+/// Turns a failed request into an error naming the URL, keeping the first
+/// line of the server's answer since it usually says what went wrong.
+pub fn request_error(url: &str, err: ureq::Error) -> AdventError {
+    let reason = match err {
+        ureq::Error::Status(code, response) => {
+            let body = response.into_string().unwrap_or_default();
+            match body.lines().next() {
+                Some(line) if !line.trim().is_empty() => format!("{}: {}", code, line.trim()),
+                _ => format!("server answered {}", code),
+            }
+        }
+        ureq::Error::Transport(transport) => {
+            let reason = transport.to_string();
+            reason
+                .strip_prefix(&format!("{}: ", url))
+                .unwrap_or(&reason)
+                .to_string()
+        }
+    };
+    AdventError::Io {
+        path: String::from(url),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::stub;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    fn inputs_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rusty_advent_{}_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[rstest]
+    fn test_fetch() {
+        let dir = inputs_dir("fetch");
+        let (url, server) = stub::serve(vec![(200, "1\n2\n3\n"), (200, "4\n")]);
+        let mut fetcher = Fetcher::new(&url, Some(String::from("abc")), Duration::from_millis(50));
+        let path = dir.join("y2022d05.txt");
+        assert_eq!(
+            fetcher.fetch(&dir, 2022, 5),
+            Ok(Fetched::Downloaded(path.clone()))
+        );
+        assert_eq!(
+            fetcher.fetch(&dir, 2022, 5),
+            Ok(Fetched::Cached(path.clone()))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        let start = Instant::now();
+        assert!(matches!(
+            fetcher.fetch(&dir, 2022, 6),
+            Ok(Fetched::Downloaded(_))
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("GET /2022/day/5/input "));
+        assert!(requests[0].contains("session=abc"));
+        assert!(requests[1].starts_with("GET /2022/day/6/input "));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[rstest]
+    #[case(
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks!",
+        "404: Please don't repeatedly request this endpoint before it unlocks!"
+    )]
+    #[case(500, "", "server answered 500")]
+    fn test_fetch_error(#[case] status: u16, #[case] body: &'static str, #[case] reason: &str) {
+        let dir = inputs_dir(&format!("fetch_error_{}", status));
+        let (url, server) = stub::serve(vec![(status, body)]);
+        let mut fetcher = Fetcher::new(&url, Some(String::from("abc")), Duration::ZERO);
+        assert!(matches!(
+            fetcher.fetch(&dir, 2022, 5),
+            Err(AdventError::Io { reason: r, .. }) if r == reason
+        ));
+        server.join().unwrap();
+        assert!(!dir.join("y2022d05.txt").exists());
+    }
+
+    #[rstest]
+    fn test_fetch_without_session() {
+        let dir = inputs_dir("fetch_without_session");
+        let mut fetcher = Fetcher::new(BASE_URL, None, Duration::ZERO);
+        assert!(matches!(
+            fetcher.fetch(&dir, 2022, 5),
+            Err(AdventError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::input::input_dir_name;
+use super::isolate::isolate;
+use super::{AdventError, Answer, Rng, Solver};
+
+/// How many inputs `fuzz` generates per challenge by default.
+pub const FUZZ_CASES: usize = 100;
+
+/// The size of the largest input `fuzz` generates by default.
+pub const FUZZ_SIZE: usize = 100;
+
+/// A generated input on which a solver's part disagrees with its reference,
+/// shrunk to as small an input as still disagrees.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub seed: u64,
+    /// The length of the input as generated, before shrinking.
+    pub generated_len: usize,
+    pub input: String,
+    pub expected: Answer,
+    pub actual: Result<Answer, AdventError>,
+}
+
+impl Mismatch {
+    /// Writes the shrunk input to `dir/yYYYYdDD/part_P_seed_S.txt`, so that it
+    /// can be solved again with `--inputs-dir dir --input-name part_P_seed_S`.
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, AdventError> {
+        let dir = dir.join(input_dir_name(self.year, self.day));
+        let path = dir.join(format!("part_{}_seed_{}.txt", self.part, self.seed));
+        fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(&path, &self.input))
+            .map_err(|err| AdventError::Io {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            })?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {} part {} disagrees with its reference on seed {}: expected {}, got ",
+            self.year, self.day, self.part, self.seed, self.expected
+        )?;
+        match &self.actual {
+            Ok(answer) => write!(f, "{}", answer)?,
+            Err(err) => write!(f, "error: {}", err)?,
+        }
+        write!(
+            f,
+            " (shrunk from {} to {} characters)",
+            self.generated_len,
+            self.input.len()
+        )
+    }
+}
+
+/// Solves `cases` generated inputs with both parts of the solver and checks
+/// them against its reference, stopping at the first mismatch. The inputs
+/// grow from size 1 up to `size`, and case `n` is generated from seed
+/// `seed + n`, wrapping around. Solvers without a generator or a reference
+/// return `None`.
+pub fn fuzz(
+    solver: &dyn Solver,
+    cases: usize,
+    size: usize,
+    seed: u64,
+) -> Option<Result<(), Mismatch>> {
+    let reference = solver.reference()?;
+    let parts = [(1, reference.part_1), (2, reference.part_2)];
+    for case in 0..cases {
+        let seed = seed.wrapping_add(case as u64);
+        let input = solver.generate(1 + case * size / cases, &mut Rng::new(seed))?;
+        for (part, reference) in parts {
+            let disagrees = |input: &str| disagreement(solver, part, reference, input);
+            if disagrees(&input).is_some() {
+                let shrunk = shrink(&input, |input| disagrees(input).is_some());
+                let (expected, actual) = disagrees(&shrunk)?;
+                return Some(Err(Mismatch {
+                    year: solver.year(),
+                    day: solver.day(),
+                    part,
+                    seed,
+                    generated_len: input.len(),
+                    input: shrunk,
+                    expected,
+                    actual,
+                }));
+            }
+        }
+    }
+    Some(Ok(()))
+}
+
+/// The reference's answer and the solver's result when the reference has an
+/// answer for the input that the solver doesn't give.
+fn disagreement(
+    solver: &dyn Solver,
+    part: u8,
+    reference: fn(&str) -> Option<Answer>,
+    input: &str,
+) -> Option<(Answer, Result<Answer, AdventError>)> {
+    let expected = isolate(|| reference(input)).ok()??;
+    let actual = isolate(|| match part {
+        1 => solver.part_1(input),
+        _ => solver.part_2(input),
+    })
+    .and_then(|result| result);
+    match &actual {
+        Ok(answer) if *answer == expected => None,
+        _ => Some((expected, actual)),
+    }
+}
+
+/// Shrinks an input that `fails` to a smaller one that still does, by
+/// removing blocks, then lines, then characters.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let input = shrink_split(input, "\n\n", &fails);
+    let input = shrink_split(&input, "\n", &fails);
+    shrink_units(input.chars().collect::<Vec<char>>(), |chars| {
+        fails(&chars.iter().collect::<String>())
+    })
+    .into_iter()
+    .collect::<String>()
+}
+
+fn shrink_split(input: &str, separator: &str, fails: impl Fn(&str) -> bool) -> String {
+    let units = input.split(separator).collect::<Vec<&str>>();
+    shrink_units(units, |units| fails(&units.join(separator))).join(separator)
+}
+
+/// Removes ever shorter runs of units for as long as what's left still
+/// `fails`, much like delta debugging.
+fn shrink_units<T: Clone>(mut units: Vec<T>, fails: impl Fn(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = units.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        let mut removed = false;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate = units[..start]
+                .iter()
+                .chain(&units[end..])
+                .cloned()
+                .collect::<Vec<T>>();
+            if fails(&candidate) {
+                units = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::{solvers, Reference};
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    /// Sums the numbers of its input, but gets it wrong once a 7 follows a 3.
+    struct Buggy;
+
+    impl Solver for Buggy {
+        fn year(&self) -> u32 {
+            2015
+        }
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Buggy"
+        }
+
+        fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+            Some(
+                (0..size)
+                    .map(|_| rng.range(0..=9).to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            )
+        }
+
+        fn reference(&self) -> Option<Reference> {
+            Some(Reference {
+                part_1: sum,
+                part_2: sum,
+            })
+        }
+
+        fn part_1(&self, input: &str) -> Result<Answer, AdventError> {
+            sum(input).ok_or_else(|| AdventError::no_solution("malformed"))
+        }
+
+        fn part_2(&self, input: &str) -> Result<Answer, AdventError> {
+            if input.contains("3\n7") {
+                return Err(AdventError::no_solution("bug"));
+            }
+            self.part_1(input)
+        }
+    }
+
+    fn sum(input: &str) -> Option<Answer> {
+        input
+            .lines()
+            .map(|line| line.parse::<u64>().ok())
+            .sum::<Option<u64>>()
+            .map(Answer::from)
+    }
+
+    #[rstest]
+    #[case("1\n2\n3\n4", "3")]
+    #[case("5\n3\n1\n3\n\n8", "3")]
+    #[case("13\n\n31", "3")]
+    fn test_shrink(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(shrink(input, |input| input.contains('3')), expected);
+    }
+
+    #[rstest]
+    fn test_fuzz_finds_and_shrinks_mismatch() {
+        let mismatch = fuzz(&Buggy, 50, 50, 0).unwrap().unwrap_err();
+        assert_eq!(mismatch.part, 2);
+        assert_eq!(mismatch.input, "3\n7");
+        assert_eq!(mismatch.expected, Answer::from(10));
+        assert_eq!(mismatch.actual, Err(AdventError::no_solution("bug")));
+        assert!(mismatch.generated_len > mismatch.input.len());
+
+        let dir = env::temp_dir().join(format!("rusty_advent_fuzz_{}", process::id()));
+        let path = mismatch.save(&dir).unwrap();
+        assert_eq!(
+            path,
+            dir.join("y2015d01")
+                .join(format!("part_2_seed_{}.txt", mismatch.seed))
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3\n7");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[rstest]
+    fn test_fuzz_wraps_seed() {
+        let mismatch = fuzz(&Buggy, 50, 50, u64::MAX - 1).unwrap().unwrap_err();
+        assert!(mismatch.seed < 50);
+    }
+
+    #[rstest]
+    fn test_every_solver_agrees_with_its_reference() {
+        for solver in solvers() {
+            if let Some(Err(mismatch)) = fuzz(solver, FUZZ_CASES, FUZZ_SIZE, 0) {
+                panic!("{}\n{}", mismatch, mismatch.input);
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::ops::RangeInclusive;
+
+use super::{solvers, AdventError};
+
+/// A small deterministic random number generator (splitmix64), so that the
+/// same seed always generates the same input on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly random number in `range`.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "cannot pick from an empty range");
+        match (hi - lo).checked_add(1) {
+            Some(span) => lo + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// A uniformly random index below `len`.
+    pub fn below(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick below 0");
+        self.range(0..=(len as u64 - 1)) as usize
+    }
+
+    /// Whether a coin that lands heads `percent` percent of the time did.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.range(1..=100) <= percent
+    }
+
+    /// A uniformly random element of `items`.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Generates a random input for the given challenge in the puzzle's format,
+/// where `size` scales how many lines, boards, moves or characters it has.
+pub fn generate(year: u32, day: u32, size: usize, seed: u64) -> Result<String, AdventError> {
+    let solver = solvers()
+        .into_iter()
+        .find(|solver| solver.year() == year && solver.day() == day)
+        .ok_or_else(|| {
+            AdventError::NoSolution(format!("there is no solver for {} day {}", year, day))
+        })?;
+    solver.generate(size, &mut Rng::new(seed)).ok_or_else(|| {
+        AdventError::NoSolution(format!("{} day {} has no input generator", year, day))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::ops::Range;
+
+    #[rstest]
+    fn test_rng_is_deterministic() {
+        let draws = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.range(1..=6)).collect::<Vec<u64>>()
+        };
+        assert_eq!(draws(7), draws(7));
+        assert_ne!(draws(7), draws(8));
+        assert!(draws(7).iter().all(|draw| (1..=6).contains(draw)));
+    }
+
+    #[rstest]
+    fn test_shuffle() {
+        let mut items = (0..50).collect::<Vec<u32>>();
+        Rng::new(0).shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<u32>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<u32>>());
+    }
+
+    #[rstest]
+    #[case(0, 0..3)]
+    #[case(1, 0..3)]
+    #[case(100, 0..3)]
+    #[case(20_000, 0..1)]
+    fn test_every_generated_input_solves(#[case] size: usize, #[case] seeds: Range<u64>) {
+        for solver in solvers() {
+            for seed in seeds.clone() {
+                let input = generate(solver.year(), solver.day(), size, seed).unwrap();
+                assert_eq!(
+                    generate(solver.year(), solver.day(), size, seed),
+                    Ok(input.clone())
+                );
+                if let Some(parsed) = solver.parse(&input) {
+                    assert_eq!(parsed, Ok(()), "{} day {}", solver.year(), solver.day());
+                }
+                assert!(
+                    solver.part_1(&input).is_ok(),
+                    "{} day {} part 1",
+                    solver.year(),
+                    solver.day()
+                );
+                assert!(
+                    solver.part_2(&input).is_ok(),
+                    "{} day {} part 2",
+                    solver.year(),
+                    solver.day()
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_generate_unknown_day() {
+        assert_eq!(
+            generate(2015, 1, 10, 0),
+            Err(AdventError::no_solution(
+                "there is no solver for 2015 day 1"
+            ))
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::timing::fmt_duration;
+use super::{AdventError, Solution};
+
+/// One solved part of one run, with the unix timestamp of the run and the
+/// commit it ran at.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Run {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub nanos: u64,
+    pub commit: Option<String>,
+    pub at: u64,
+}
+
+impl Run {
+    /// The runs of every part the solutions solved.
+    pub fn from_solutions(solutions: &[Solution], commit: Option<&str>, at: u64) -> Vec<Run> {
+        solutions
+            .iter()
+            .flat_map(|solution| {
+                [
+                    (1, &solution.part1, solution.timing.part1),
+                    (2, &solution.part2, solution.timing.part2),
+                ]
+                .into_iter()
+                .filter_map(move |(part, answer, duration)| match (answer, duration) {
+                    (Some(Ok(answer)), Some(duration)) => Some(Run {
+                        year: solution.year,
+                        day: solution.day,
+                        part,
+                        answer: answer.to_string(),
+                        nanos: duration.as_nanos() as u64,
+                        commit: commit.map(String::from),
+                        at,
+                    }),
+                    _ => None,
+                })
+            })
+            .collect::<Vec<Run>>()
+    }
+}
+
+/// Every run so far, stored as TOML with one `[[run]]` table each. New runs
+/// are appended, so the history is in the order the runs happened.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct History {
+    #[serde(default, rename = "run")]
+    runs: Vec<Run>,
+}
+
+impl History {
+    /// Loads the history at `path`, where a missing file has no runs.
+    pub fn load(path: &Path) -> Result<History, AdventError> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str::<History>(&text).map_err(|err| {
+                AdventError::parse_at(
+                    &text,
+                    err.span().map_or(0, |span| span.start),
+                    err.message(),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(io_error(path, err)),
+        }
+    }
+
+    /// Appends runs to the history at `path` without rewriting the runs
+    /// already in it.
+    pub fn append(path: &Path, runs: Vec<Run>) -> Result<(), AdventError> {
+        if runs.is_empty() {
+            return Ok(());
+        }
+        let text = toml::to_string(&History { runs }).map_err(|err| AdventError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| io_error(path, err))?;
+        let separator = match file.metadata().map(|metadata| metadata.len()) {
+            Ok(0) => "",
+            _ => "\n",
+        };
+        write!(file, "{}{}", separator, text).map_err(|err| io_error(path, err))
+    }
+
+    /// Shows the last `last` runs of every part of the selected challenges,
+    /// where 0 selects every year or day. A run is flagged when it took more
+    /// than `threshold` percent longer than the best run before it.
+    pub fn report(&self, year: u32, day: u32, threshold: f64, last: usize) -> String {
+        let mut parts = BTreeMap::<(u32, u32, u8), Vec<&Run>>::new();
+        self.runs
+            .iter()
+            .filter(|run| (year == 0 || run.year == year) && (day == 0 || run.day == day))
+            .for_each(|run| {
+                parts
+                    .entry((run.year, run.day, run.part))
+                    .or_default()
+                    .push(run)
+            });
+        let mut report = String::new();
+        for ((year, day, part), runs) in parts {
+            let best = runs.iter().map(|run| run.nanos).min().unwrap_or_default();
+            let latest = runs.last().map_or(0, |run| run.nanos);
+            if !report.is_empty() {
+                report.push('\n');
+            }
+            let _ = writeln!(
+                report,
+                "{} day {} part {}: {} runs, best {}, latest {}",
+                year,
+                day,
+                part,
+                runs.len(),
+                fmt_nanos(best),
+                fmt_nanos(latest)
+            );
+            let flags = runs
+                .iter()
+                .scan(None, |best: &mut Option<u64>, run| {
+                    let flag = best
+                        .filter(|best| run.nanos as f64 > *best as f64 * (1.0 + threshold / 100.0))
+                        .map(|best| {
+                            format!(
+                                "  SLOWER +{:.0}% than {}",
+                                (run.nanos as f64 / best as f64 - 1.0) * 100.0,
+                                fmt_nanos(best)
+                            )
+                        });
+                    *best = Some(best.map_or(run.nanos, |best| best.min(run.nanos)));
+                    Some(flag.unwrap_or_default())
+                })
+                .collect::<Vec<String>>();
+            runs.iter()
+                .zip(flags)
+                .skip(runs.len().saturating_sub(last))
+                .for_each(|(run, flag)| {
+                    let _ = writeln!(
+                        report,
+                        "  {}  {:<14}{:>12}  {}{}",
+                        fmt_timestamp(run.at),
+                        run.commit.as_deref().unwrap_or("-"),
+                        fmt_nanos(run.nanos),
+                        run.answer.replace('\n', " / "),
+                        flag
+                    );
+                });
+        }
+        report
+    }
+}
+
+/// The short hash of the commit checked out in `root`, suffixed with
+/// `-dirty` when there are uncommitted changes, or `None` outside of git.
+pub fn git_commit(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let clean = Command::new("git")
+        .args(["diff", "--quiet", "HEAD"])
+        .current_dir(root)
+        .status()
+        .is_ok_and(|status| status.success());
+    Some(if clean { hash } else { hash + "-dirty" })
+}
+
+fn fmt_nanos(nanos: u64) -> String {
+    fmt_duration(Some(Duration::from_nanos(nanos)))
+}
+
+/// Formats a unix timestamp as a UTC date and time, e.g. `2022-12-05 06:00`.
+fn fmt_timestamp(at: u64) -> String {
+    // Converts days since the epoch to a civil date, following
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (at / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let seconds = at % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+fn io_error(path: &Path, err: io::Error) -> AdventError {
+    AdventError::Io {
+        path: path.display().to_string(),
+        reason: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::{Answer, Timing};
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    fn run(part: u8, answer: &str, millis: u64, at: u64) -> Run {
+        Run {
+            year: 2022,
+            day: 5,
+            part,
+            answer: String::from(answer),
+            nanos: millis * 1_000_000,
+            commit: Some(String::from("abc1234")),
+            at,
+        }
+    }
+
+    #[rstest]
+    #[case(0, "1970-01-01 00:00")]
+    #[case(1_670_220_000, "2022-12-05 06:00")]
+    #[case(1_709_210_096, "2024-02-29 12:34")]
+    fn test_fmt_timestamp(#[case] at: u64, #[case] expected: &str) {
+        assert_eq!(fmt_timestamp(at), expected);
+    }
+
+    #[rstest]
+    fn test_from_solutions() {
+        let solution = Solution {
+            year: 2022,
+            day: 5,
+            title: "Supply Stacks",
+            part1: Some(Ok(Answer::from("CMZ"))),
+            part2: Some(Err(AdventError::no_solution("stack 4 is empty"))),
+            timing: Timing {
+                parse: None,
+                part1: Some(Duration::from_millis(2)),
+                part2: Some(Duration::from_millis(3)),
+            },
+        };
+        assert_eq!(
+            Run::from_solutions(&[solution], Some("abc1234"), 0),
+            vec![run(1, "CMZ", 2, 0)]
+        );
+    }
+
+    #[rstest]
+    fn test_append_and_load() {
+        let path = env::temp_dir().join(format!("rusty_advent_history_{}.toml", process::id()));
+        let _ = fs::remove_file(&path);
+        History::append(&path, vec![run(1, "CMZ", 2, 0)]).unwrap();
+        History::append(&path, vec![]).unwrap();
+        History::append(&path, vec![run(1, "CMZ", 3, 60), run(2, "MCD", 4, 60)]).unwrap();
+        assert_eq!(
+            History::load(&path).map(|history| history.runs),
+            Ok(vec![
+                run(1, "CMZ", 2, 0),
+                run(1, "CMZ", 3, 60),
+                run(2, "MCD", 4, 60)
+            ])
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[rstest]
+    fn test_report() {
+        let history = History {
+            runs: vec![
+                run(1, "CMZ", 4, 0),
+                run(1, "CMZ", 2, 60),
+                run(1, "CMZ", 2, 120),
+                run(1, "CMZ", 3, 180),
+                run(2, "MCD", 5, 180),
+            ],
+        };
+        assert_eq!(
+            history.report(2022, 5, 10.0, 3),
+            "2022 day 5 part 1: 4 runs, best 2.00ms, latest 3.00ms\n\
+             \x20 1970-01-01 00:01  abc1234             2.00ms  CMZ\n\
+             \x20 1970-01-01 00:02  abc1234             2.00ms  CMZ\n\
+             \x20 1970-01-01 00:03  abc1234             3.00ms  CMZ  SLOWER +50% than 2.00ms\n\
+             \n\
+             2022 day 5 part 2: 1 runs, best 5.00ms, latest 5.00ms\n\
+             \x20 1970-01-01 00:03  abc1234             5.00ms  MCD\n"
+        );
+        assert_eq!(history.report(2021, 0, 10.0, 3), "");
+    }
+}
@@ -0,0 +1,172 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use super::AdventError;
+
+/// Where a challenge reads its puzzle input from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    /// Read `yYYYYdDD.txt` from the given inputs directory.
+    Dir(PathBuf),
+    /// Read `yYYYYdDD/{name}.txt` from the given inputs directory.
+    Named { dir: PathBuf, name: String },
+    /// Read the given file, regardless of the challenge.
+    File(PathBuf),
+    /// Read everything from standard input.
+    Stdin,
+}
+
+impl Input {
+    /// Parses a command line input argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            _ => Input::File(PathBuf::from(arg)),
+        }
+    }
+
+    /// The file a challenge's input is read from, or `None` for stdin.
+    pub fn path(&self, year: u32, day: u32) -> Option<PathBuf> {
+        match self {
+            Input::Dir(dir) => Some(dir.join(input_file_name(year, day))),
+            Input::Named { dir, name } => Some(
+                dir.join(input_dir_name(year, day))
+                    .join(format!("{}.txt", name)),
+            ),
+            Input::File(path) => Some(path.clone()),
+            Input::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, year: u32, day: u32) -> Result<String, AdventError> {
+        let Some(path) = self.path(year, day) else {
+            let mut input = String::new();
+            return match io::stdin().read_to_string(&mut input) {
+                Ok(_) => Ok(input),
+                Err(err) => Err(AdventError::MissingInput {
+                    path: String::from("<stdin>"),
+                    reason: err.to_string(),
+                }),
+            };
+        };
+        fs::read_to_string(&path).map_err(|err| AdventError::MissingInput {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })
+    }
+}
+
+impl Default for Input {
+    fn default() -> Input {
+        Input::Dir(PathBuf::from("inputs"))
+    }
+}
+
+/// The conventional file name of a challenge's input, e.g. `y2022d05.txt`.
+pub fn input_file_name(year: u32, day: u32) -> String {
+    format!("{}.txt", input_dir_name(year, day))
+}
+
+/// The directory holding a challenge's named inputs, e.g. `y2022d05`.
+pub fn input_dir_name(year: u32, day: u32) -> String {
+    format!("y{}d{:02}", year, day)
+}
+
+/// Splits `line` on `delimiter` into its fields, each paired with its byte
+/// offset in `line`, so that parse errors can point at the field.
+pub fn split_columns(line: &str, delimiter: char) -> Vec<(usize, &str)> {
+    let mut offset = 0;
+    line.split(delimiter)
+        .map(|field| {
+            let column = (offset, field);
+            offset += field.len() + delimiter.len_utf8();
+            column
+        })
+        .collect::<Vec<(usize, &str)>>()
+}
+
+/// Every input of a challenge in the inputs directory, by file stem: its
+/// `yYYYYdDD.txt` input, followed by its named inputs in order.
+pub fn inputs_of(dir: &Path, year: u32, day: u32) -> Vec<(String, Input)> {
+    let mut names = fs::read_dir(dir.join(input_dir_name(year, day)))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect::<Vec<String>>();
+    names.sort_unstable();
+    dir.join(input_file_name(year, day))
+        .is_file()
+        .then(|| (input_dir_name(year, day), Input::Dir(dir.to_path_buf())))
+        .into_iter()
+        .chain(names.into_iter().map(|name| {
+            let input = Input::Named {
+                dir: dir.to_path_buf(),
+                name: name.clone(),
+            };
+            (name, input)
+        }))
+        .collect::<Vec<(String, Input)>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    #[rstest]
+    #[case("-", Input::Stdin)]
+    #[case("alice.txt", Input::File(PathBuf::from("alice.txt")))]
+    fn test_from_arg(#[case] arg: &str, #[case] expected: Input) {
+        assert_eq!(Input::from_arg(arg), expected);
+    }
+
+    #[rstest]
+    #[case(2022, 5, "y2022d05.txt")]
+    #[case(2021, 12, "y2021d12.txt")]
+    fn test_input_file_name(#[case] year: u32, #[case] day: u32, #[case] expected: &str) {
+        assert_eq!(input_file_name(year, day), expected);
+    }
+
+    #[rstest]
+    #[case("move 1 from 2", ' ', vec![(0, "move"), (5, "1"), (7, "from"), (12, "2")])]
+    #[case("1,,2", ',', vec![(0, "1"), (2, ""), (3, "2")])]
+    #[case("", ' ', vec![(0, "")])]
+    fn test_split_columns(
+        #[case] line: &str,
+        #[case] delimiter: char,
+        #[case] expected: Vec<(usize, &str)>,
+    ) {
+        assert_eq!(split_columns(line, delimiter), expected);
+    }
+
+    #[rstest]
+    fn test_inputs_of() {
+        let dir = env::temp_dir().join(format!("rusty_advent_inputs_{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("y2022d05")).unwrap();
+        fs::write(dir.join("y2022d05.txt"), "mine").unwrap();
+        fs::write(dir.join("y2022d05/edge.txt"), "edge").unwrap();
+        fs::write(dir.join("y2022d05/alice.txt"), "alice").unwrap();
+        fs::write(dir.join("y2022d05/notes.md"), "notes").unwrap();
+        let inputs = inputs_of(&dir, 2022, 5);
+        assert_eq!(
+            inputs
+                .iter()
+                .map(|(name, input)| (name.as_str(), input.read(2022, 5).unwrap()))
+                .collect::<Vec<(&str, String)>>(),
+            vec![
+                ("y2022d05", String::from("mine")),
+                ("alice", String::from("alice")),
+                ("edge", String::from("edge")),
+            ]
+        );
+        assert_eq!(inputs_of(&dir, 2022, 6), Vec::new());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
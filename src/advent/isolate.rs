@@ -0,0 +1,83 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+use super::AdventError;
+
+thread_local! {
+    /// Whether this thread is inside `isolate`, where panics are caught.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    /// Where the last caught panic on this thread happened.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// Runs `task` behind a panic boundary, turning a panic into an
+/// `AdventError::Panic` with its message. Caught panics aren't printed by
+/// the panic hook, any others still are.
+pub fn isolate<R, F: FnOnce() -> R>(task: F) -> Result<R, AdventError> {
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.with(Cell::get) {
+                let location = info
+                    .location()
+                    .map(|location| format!("{}:{}", location.file(), location.line()));
+                LOCATION.with(|last| *last.borrow_mut() = location);
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+    let outer = ISOLATED.with(|isolated| isolated.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(task));
+    ISOLATED.with(|isolated| isolated.set(outer));
+    result.map_err(|payload| {
+        let message = panic_message(payload.as_ref());
+        match LOCATION.with(|last| last.borrow_mut().take()) {
+            Some(location) => AdventError::Panic(format!("{} at {}", message, location)),
+            None => AdventError::Panic(message),
+        }
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| String::from("unknown panic"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_isolate() {
+        assert_eq!(isolate(|| 7), Ok(7));
+        let line = line!() + 1;
+        let result = isolate(|| -> u32 { panic!("stack {} is empty", 4) });
+        assert_eq!(
+            result,
+            Err(AdventError::Panic(format!(
+                "stack 4 is empty at {}:{}",
+                file!(),
+                line
+            )))
+        );
+    }
+
+    #[rstest]
+    fn test_isolate_index() {
+        let elves: &[u32] = &[1, 2];
+        let result = isolate(|| elves[..3].iter().sum::<u32>());
+        assert!(matches!(
+            result,
+            Err(AdventError::Panic(message)) if message.starts_with("range end index 3 out of range")
+        ));
+    }
+}
@@ -0,0 +1,280 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::timing::fmt_duration;
+use super::{fmt_part, AdventError, Answer, Solution};
+
+/// How a run's solutions are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = &'static str;
+
+    fn from_str(format_str: &str) -> Result<Format, &'static str> {
+        match format_str {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err("can only parse 'text', 'json', 'csv', and 'tsv' as Formats"),
+        }
+    }
+}
+
+const COLUMNS: [&str; 10] = [
+    "year", "day", "title", "part1", "part2", "parse_ns", "part1_ns", "part2_ns", "status", "error",
+];
+
+/// Renders solutions in the given format. The text format is the `Display`
+/// of each solution; the others list one record per solution, with
+/// unselected or failed answers and unmeasured timings left empty (or
+/// `null` in JSON). Numeric answers are JSON numbers.
+pub fn render(solutions: &[Solution], format: Format) -> String {
+    match format {
+        Format::Text => solutions
+            .iter()
+            .map(|solution| format!("{}\n", solution))
+            .collect::<String>(),
+        Format::Json => render_json(solutions),
+        Format::Csv => render_delimited(solutions, ',', escape_csv),
+        Format::Tsv => render_delimited(solutions, '\t', escape_tsv),
+    }
+}
+
+/// Tabulates the answers and timings of solutions paired with the name of
+/// the input they solved, as made by running on every input.
+pub fn inputs_report(runs: &[(String, Solution)]) -> String {
+    let mut report = format!(
+        "{:<6}{:>4}  {:<12}{:<20}{:<20}{:>12}{:>12}\n",
+        "year", "day", "input", "part 1", "part 2", "part 1 time", "part 2 time"
+    );
+    runs.iter().for_each(|(name, solution)| {
+        let _ = writeln!(
+            report,
+            "{:<6}{:>4}  {:<12}{:<20}{:<20}{:>12}{:>12}",
+            solution.year,
+            solution.day,
+            name,
+            fmt_part(&solution.part1),
+            fmt_part(&solution.part2),
+            fmt_duration(solution.timing.part1),
+            fmt_duration(solution.timing.part2),
+        );
+    });
+    report
+}
+
+/// A single value of a record, which is missing when `None`.
+enum Field {
+    Number(Option<String>),
+    Text(Option<String>),
+}
+
+impl Field {
+    fn value(self) -> Option<String> {
+        match self {
+            Field::Number(value) | Field::Text(value) => value,
+        }
+    }
+}
+
+/// The fields of a solution, in `COLUMNS` order.
+fn fields(solution: &Solution) -> [Field; 10] {
+    let answer = |part: &Option<Result<Answer, AdventError>>| match part {
+        Some(Ok(answer)) if answer.as_number().is_some() => Field::Number(Some(answer.to_string())),
+        Some(Ok(answer)) => Field::Text(Some(answer.to_string())),
+        _ => Field::Text(None),
+    };
+    let nanos = |duration: Option<Duration>| duration.map(|d| d.as_nanos().to_string());
+    let errors = solution
+        .errors()
+        .iter()
+        .map(|err| err.to_string())
+        .collect::<Vec<String>>();
+    [
+        Field::Number(Some(solution.year.to_string())),
+        Field::Number(Some(solution.day.to_string())),
+        Field::Text(Some(solution.title.to_string())),
+        answer(&solution.part1),
+        answer(&solution.part2),
+        Field::Number(nanos(solution.timing.parse)),
+        Field::Number(nanos(solution.timing.part1)),
+        Field::Number(nanos(solution.timing.part2)),
+        Field::Text(Some(String::from(status(solution)))),
+        Field::Text((!errors.is_empty()).then(|| errors.join("; "))),
+    ]
+}
+
+/// `ok`, `timeout` if a part ran out of time, or `error` if one failed.
+fn status(solution: &Solution) -> &'static str {
+    let errors = solution.errors();
+    if errors.is_empty() {
+        "ok"
+    } else if errors
+        .iter()
+        .any(|err| matches!(err, AdventError::Timeout(_)))
+    {
+        "timeout"
+    } else {
+        "error"
+    }
+}
+
+fn render_json(solutions: &[Solution]) -> String {
+    let records = solutions
+        .iter()
+        .map(|solution| {
+            let members = COLUMNS
+                .iter()
+                .zip(fields(solution))
+                .map(|(column, field)| match field {
+                    Field::Text(Some(value)) => format!("\"{}\":{}", column, escape_json(&value)),
+                    Field::Number(Some(value)) => format!("\"{}\":{}", column, value),
+                    Field::Text(None) | Field::Number(None) => format!("\"{}\":null", column),
+                })
+                .collect::<Vec<String>>();
+            format!("  {{{}}}", members.join(","))
+        })
+        .collect::<Vec<String>>();
+    if records.is_empty() {
+        String::from("[]\n")
+    } else {
+        format!("[\n{}\n]\n", records.join(",\n"))
+    }
+}
+
+fn render_delimited(solutions: &[Solution], delimiter: char, escape: fn(&str) -> String) -> String {
+    let mut output = COLUMNS.join(&delimiter.to_string());
+    output.push('\n');
+    solutions.iter().for_each(|solution| {
+        let record = fields(solution)
+            .into_iter()
+            .map(|field| {
+                field
+                    .value()
+                    .map_or_else(String::new, |value| escape(&value))
+            })
+            .collect::<Vec<String>>();
+        let _ = writeln!(output, "{}", record.join(&delimiter.to_string()));
+    });
+    output
+}
+
+fn escape_json(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    value.chars().for_each(|ch| match ch {
+        '"' => escaped.push_str("\\\""),
+        '\\' => escaped.push_str("\\\\"),
+        '\n' => escaped.push_str("\\n"),
+        '\r' => escaped.push_str("\\r"),
+        '\t' => escaped.push_str("\\t"),
+        ch if (ch as u32) < 0x20 => {
+            let _ = write!(escaped, "\\u{:04x}", ch as u32);
+        }
+        ch => escaped.push(ch),
+    });
+    escaped.push('"');
+    escaped
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_tsv(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::Timing;
+    use rstest::rstest;
+
+    fn solution() -> Solution {
+        Solution {
+            year: 2022,
+            day: 5,
+            title: "Supply Stacks",
+            part1: Some(Ok(Answer::from("CMZ"))),
+            part2: Some(Err(AdventError::no_solution("cargo stack 4 is empty"))),
+            timing: Timing {
+                parse: None,
+                part1: Some(Duration::from_nanos(1500)),
+                part2: Some(Duration::from_nanos(20)),
+            },
+        }
+    }
+
+    #[rstest]
+    #[case(Format::Json, "[\n  {\"year\":2022,\"day\":5,\"title\":\"Supply Stacks\",\"part1\":\"CMZ\",\"part2\":null,\"parse_ns\":null,\"part1_ns\":1500,\"part2_ns\":20,\"status\":\"error\",\"error\":\"no solution: cargo stack 4 is empty\"}\n]\n")]
+    #[case(Format::Csv, "year,day,title,part1,part2,parse_ns,part1_ns,part2_ns,status,error\n2022,5,Supply Stacks,CMZ,,,1500,20,error,no solution: cargo stack 4 is empty\n")]
+    #[case(Format::Tsv, "year\tday\ttitle\tpart1\tpart2\tparse_ns\tpart1_ns\tpart2_ns\tstatus\terror\n2022\t5\tSupply Stacks\tCMZ\t\t\t1500\t20\terror\tno solution: cargo stack 4 is empty\n")]
+    fn test_render(#[case] format: Format, #[case] expected: &str) {
+        assert_eq!(render(&[solution()], format), expected);
+    }
+
+    #[rstest]
+    fn test_render_numeric_answer() {
+        let solution = Solution {
+            part1: Some(Ok(Answer::from(-7))),
+            ..solution()
+        };
+        assert!(render(&[solution], Format::Json).contains("\"part1\":-7,"));
+    }
+
+    #[rstest]
+    fn test_render_timeout() {
+        let solution = Solution {
+            part2: Some(Err(AdventError::Timeout(Duration::from_secs(1)))),
+            ..solution()
+        };
+        assert!(render(&[solution], Format::Csv)
+            .ends_with(",CMZ,,,1500,20,timeout,timed out after 1s\n"));
+    }
+
+    #[rstest]
+    fn test_inputs_report() {
+        let runs = [
+            (String::from("y2022d05"), solution()),
+            (String::from("alice"), solution()),
+        ];
+        assert_eq!(
+            inputs_report(&runs),
+            "year   day  input       part 1              part 2               part 1 time part 2 time\n\
+             2022     5  y2022d05    CMZ                 FAILED                    1.50µs     20.00ns\n\
+             2022     5  alice       CMZ                 FAILED                    1.50µs     20.00ns\n"
+        );
+    }
+
+    #[rstest]
+    #[case("a \"b\"\n", "\"a \\\"b\\\"\\n\"")]
+    #[case("\u{1}", "\"\\u0001\"")]
+    fn test_escape_json(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(escape_json(value), expected);
+    }
+
+    #[rstest]
+    #[case("CMZ", "CMZ")]
+    #[case("a,b", "\"a,b\"")]
+    #[case("a \"b\"", "\"a \"\"b\"\"\"")]
+    fn test_escape_csv(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(escape_csv(value), expected);
+    }
+}
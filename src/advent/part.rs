@@ -0,0 +1,48 @@
+use std::str::FromStr;
+
+/// Which parts of a challenge a run should solve.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl Part {
+    pub fn includes_part_1(&self) -> bool {
+        matches!(self, Part::One | Part::Both)
+    }
+
+    pub fn includes_part_2(&self) -> bool {
+        matches!(self, Part::Two | Part::Both)
+    }
+}
+
+impl FromStr for Part {
+    type Err = &'static str;
+
+    fn from_str(part_str: &str) -> Result<Part, &'static str> {
+        match part_str {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => Err("can only parse '1', '2', and 'both' as Parts"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1", Ok(Part::One))]
+    #[case("2", Ok(Part::Two))]
+    #[case("both", Ok(Part::Both))]
+    #[case("3", Err("can only parse '1', '2', and 'both' as Parts"))]
+    fn test_from_str(#[case] part_str: &str, #[case] expected: Result<Part, &'static str>) {
+        assert_eq!(part_str.parse::<Part>(), expected);
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `task` on every item using up to `jobs` worker threads. Each result
+/// is handed to `on_done` in item order as soon as it and every result
+/// before it are ready, so output streams without losing its ordering.
+pub fn run_ordered<T, R, F, G>(items: &[T], jobs: usize, task: F, mut on_done: G) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    G: FnMut(&R),
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let (sender, next, task) = (sender.clone(), &next, &task);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                if sender.send((idx, task(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        let mut results = items.iter().map(|_| None).collect::<Vec<Option<R>>>();
+        let mut emitted = 0;
+        for (idx, result) in receiver {
+            results[idx] = Some(result);
+            while let Some(Some(result)) = results.get(emitted) {
+                on_done(result);
+                emitted += 1;
+            }
+        }
+        results.into_iter().flatten().collect::<Vec<R>>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::time::Duration;
+
+    #[rstest]
+    #[case(1)]
+    #[case(3)]
+    #[case(16)]
+    fn test_run_ordered(#[case] jobs: usize) {
+        let items = [30, 0, 20, 5, 10, 0];
+        let mut streamed = Vec::new();
+        let results = run_ordered(
+            &items,
+            jobs,
+            |millis| {
+                thread::sleep(Duration::from_millis(*millis));
+                millis * 2
+            },
+            |result| streamed.push(*result),
+        );
+        assert_eq!(results, vec![60, 0, 40, 10, 20, 0]);
+        assert_eq!(streamed, results);
+    }
+}
@@ -0,0 +1,473 @@
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::input::input_file_name;
+use super::AdventError;
+
+/// Generates a new day from `.template.rs` under `root`, declares it in its
+/// year module and registers it in the year's `solvers!` list, creating and
+/// registering the year module and its cargo feature too if it doesn't exist
+/// yet. An empty input placeholder is created in `inputs_dir`. Nothing is
+/// written if the day already exists. Returns the files that were written.
+pub fn scaffold(
+    root: &Path,
+    inputs_dir: &Path,
+    year: u32,
+    day: u32,
+    title: &str,
+) -> Result<Vec<PathBuf>, AdventError> {
+    let advent_dir = root.join("src").join("advent");
+    let year_path = advent_dir.join(format!("advent_{}.rs", year));
+    let day_path = advent_dir
+        .join(format!("advent_{}", year))
+        .join(format!("day_{:02}.rs", day));
+    if day_path.exists() {
+        return Err(io_error(&day_path, "day already exists"));
+    }
+    let solver = solver_name(title, day);
+    let day_source = read(&root.join(".template.rs"))?
+        .replace("__SOLVER__", &solver)
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string())
+        .replace("__TITLE__", &title.escape_default().to_string());
+    let entry = format!("day_{:02}::{}", day, solver);
+    let mut writes = Vec::new();
+    if year_path.exists() {
+        let year_source = read(&year_path)?;
+        writes.push((
+            year_path.clone(),
+            register_day(&year_path, &year_source, &entry)?,
+        ));
+    } else {
+        let advent_path = root.join("src").join("advent.rs");
+        let advent_source = read(&advent_path)?;
+        let solver_path = advent_dir.join("solver.rs");
+        let solver_source = read(&solver_path)?;
+        let manifest_path = root.join("Cargo.toml");
+        let manifest = read(&manifest_path)?;
+        let year_source = register_day(&year_path, "solvers! {\n}\n", &entry)?;
+        writes.push((year_path, year_source));
+        writes.push((
+            advent_path.clone(),
+            register_year(&advent_path, &advent_source, year)?,
+        ));
+        writes.push((
+            solver_path.clone(),
+            register_macro_gate(&solver_path, &solver_source, year)?,
+        ));
+        writes.push((
+            manifest_path.clone(),
+            register_feature(&manifest_path, &manifest, year)?,
+        ));
+    }
+    writes.push((day_path, day_source));
+
+    let mut written = Vec::new();
+    for (path, source) in writes {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| io_error(dir, &err.to_string()))?;
+        }
+        fs::write(&path, source).map_err(|err| io_error(&path, &err.to_string()))?;
+        written.push(path);
+    }
+    let input_path = inputs_dir.join(input_file_name(year, day));
+    fs::create_dir_all(inputs_dir).map_err(|err| io_error(inputs_dir, &err.to_string()))?;
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&input_path)
+    {
+        Ok(_) => written.push(input_path),
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => (),
+        Err(err) => return Err(io_error(&input_path, &err.to_string())),
+    }
+    Ok(written)
+}
+
+/// The solver struct name for a title, e.g. `SupplyStacks` for
+/// "Supply Stacks", falling back to `Day05` when the title has no usable name.
+pub fn solver_name(title: &str, day: u32) -> String {
+    let name = title
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase())
+                .into_iter()
+                .chain(chars)
+        })
+        .collect::<String>();
+    match name.chars().next() {
+        Some(first) if first.is_ascii_alphabetic() => name,
+        _ => format!("Day{:02}", day),
+    }
+}
+
+/// Declares the module of `entry` and adds `entry` to the `solvers!` list in
+/// `source`, keeping both ordered.
+fn register_day(path: &Path, source: &str, entry: &str) -> Result<String, AdventError> {
+    let (head, rest) = source
+        .split_once("solvers! {")
+        .ok_or_else(|| io_error(path, "no solvers! list to register the day in"))?;
+    let (list, tail) = rest
+        .split_once('}')
+        .ok_or_else(|| io_error(path, "solvers! list is not closed"))?;
+    let module = entry.split("::").next().unwrap_or(entry);
+    let mut entries = list
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect::<Vec<&str>>();
+    if entries
+        .iter()
+        .any(|entry| entry.split("::").next() == Some(module))
+    {
+        return Err(io_error(path, "day is already registered"));
+    }
+    entries.push(entry);
+    entries.sort_unstable();
+    let list = entries
+        .iter()
+        .map(|entry| format!("    {},\n", entry))
+        .collect::<String>();
+    let declaration = format!("pub mod {};", module);
+    let mut mods = head
+        .lines()
+        .filter(|line| line.starts_with("pub mod "))
+        .chain([declaration.as_str()])
+        .collect::<Vec<&str>>();
+    mods.sort_unstable();
+    let preamble = head
+        .lines()
+        .filter(|line| !line.starts_with("pub mod "))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let preamble = match preamble.trim_end() {
+        "" => String::new(),
+        preamble => format!("{}\n\n", preamble),
+    };
+    Ok(format!(
+        "{}{}\n\nsolvers! {{\n{}}}{}",
+        preamble,
+        mods.join("\n"),
+        list,
+        tail
+    ))
+}
+
+/// Adds the year's feature-gated `mod` declaration and `REGISTRY` entry to
+/// `advent.rs`.
+fn register_year(path: &Path, source: &str, year: u32) -> Result<String, AdventError> {
+    let mut lines = source.lines().map(String::from).collect::<Vec<String>>();
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod advent_"))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<usize>>();
+    let (first, last) = match (mods.first(), mods.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => {
+            return Err(io_error(
+                path,
+                "no year modules to register the year next to",
+            ))
+        }
+    };
+    let first = match first.checked_sub(1) {
+        Some(cfg) if lines[cfg].starts_with("#[cfg(feature") => cfg,
+        _ => first,
+    };
+    let mut years = lines[first..=last]
+        .iter()
+        .filter_map(|line| line.strip_prefix("pub mod advent_")?.strip_suffix(';'))
+        .map(String::from)
+        .collect::<Vec<String>>();
+    years.push(year.to_string());
+    years.sort_unstable();
+    years.dedup();
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("const REGISTRY"))
+        .ok_or_else(|| io_error(path, "no REGISTRY to register the year in"))?;
+    let end = (start..lines.len())
+        .find(|&idx| lines[idx].ends_with("];"))
+        .ok_or_else(|| io_error(path, "REGISTRY is not closed"))?;
+    let decl = lines[start]
+        .split_once(" = ")
+        .map(|(decl, _)| decl.to_string())
+        .ok_or_else(|| io_error(path, "REGISTRY has no value"))?;
+    let registry = std::iter::once(format!("{} = &[", decl))
+        .chain(years.iter().flat_map(|year| {
+            [
+                format!("    {}", feature_gate(year)),
+                format!("    advent_{}::SOLVERS,", year),
+            ]
+        }))
+        .chain(std::iter::once(String::from("];")))
+        .collect::<Vec<String>>();
+
+    // The registry comes after the mod declarations, so replace it first.
+    lines.splice(start..=end, registry);
+    lines.splice(
+        first..=last,
+        years
+            .iter()
+            .flat_map(|year| [feature_gate(year), format!("pub mod advent_{};", year)]),
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the year's feature to the `cfg(any(...))` that keeps the `solvers!`
+/// macro out of builds without any year.
+fn register_macro_gate(path: &Path, source: &str, year: u32) -> Result<String, AdventError> {
+    let mut lines = source.lines().map(String::from).collect::<Vec<String>>();
+    let gate = lines
+        .iter()
+        .position(|line| line.starts_with("macro_rules! solvers"))
+        .and_then(|idx| idx.checked_sub(1))
+        .filter(|&idx| lines[idx].starts_with("#[cfg(any("))
+        .ok_or_else(|| io_error(path, "no cfg(any(...)) on the solvers! macro"))?;
+    let mut features = lines[gate]
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .map(String::from)
+        .chain([format!("y{}", year)])
+        .collect::<Vec<String>>();
+    features.sort_unstable();
+    features.dedup();
+    lines[gate] = format!(
+        "#[cfg(any({}))]",
+        features
+            .iter()
+            .map(|feature| format!("feature = \"{}\"", feature))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds the year's `yYYYY` feature to `Cargo.toml`, enabled by default.
+fn register_feature(path: &Path, source: &str, year: u32) -> Result<String, AdventError> {
+    let feature = format!("y{}", year);
+    let mut lines = source.lines().map(String::from).collect::<Vec<String>>();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "[features]")
+        .ok_or_else(|| io_error(path, "no [features] to add the year to"))?;
+    let end = (start + 1..lines.len())
+        .find(|&idx| lines[idx].starts_with('['))
+        .unwrap_or(lines.len());
+    let default = (start + 1..end)
+        .find(|&idx| lines[idx].starts_with("default = ["))
+        .ok_or_else(|| io_error(path, "no default features to enable the year in"))?;
+    let mut defaults = lines[default]
+        .trim_start_matches("default = [")
+        .trim_end_matches(']')
+        .split(',')
+        .map(|name| name.trim().trim_matches('"'))
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect::<Vec<String>>();
+    defaults.push(feature.clone());
+    defaults.sort_unstable();
+    defaults.dedup();
+    lines[default] = format!(
+        "default = [{}]",
+        defaults
+            .iter()
+            .map(|name| format!("\"{}\"", name))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let features = (start + 1..end)
+        .filter(|&idx| is_year_feature(&lines[idx]))
+        .collect::<Vec<usize>>();
+    let declared = format!("{} = []", feature);
+    if !features.iter().any(|&idx| lines[idx] == declared) {
+        let at = features
+            .iter()
+            .find(|&&idx| lines[idx] > declared)
+            .copied()
+            .or(features.last().map(|idx| idx + 1))
+            .unwrap_or(default + 1);
+        lines.insert(at, declared);
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+fn feature_gate(year: &str) -> String {
+    format!("#[cfg(feature = \"y{}\")]", year)
+}
+
+/// Whether a line of `[features]` declares a year, like `y2022 = []`.
+fn is_year_feature(line: &str) -> bool {
+    line.strip_prefix('y')
+        .and_then(|rest| rest.strip_suffix(" = []"))
+        .is_some_and(|year| year.chars().all(|ch| ch.is_ascii_digit()))
+}
+
+fn read(path: &Path) -> Result<String, AdventError> {
+    fs::read_to_string(path).map_err(|err| io_error(path, &err.to_string()))
+}
+
+fn io_error(path: &Path, reason: &str) -> AdventError {
+    AdventError::Io {
+        path: path.display().to_string(),
+        reason: String::from(reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    const ADVENT: &str = "mod timing;\n\n#[cfg(feature = \"y2021\")]\npub mod advent_2021;\n#[cfg(feature = \"y2022\")]\npub mod advent_2022;\n\npub use solver::Solver;\n\nconst REGISTRY: &[&[&dyn Solver]] = &[\n    #[cfg(feature = \"y2021\")]\n    advent_2021::SOLVERS,\n    #[cfg(feature = \"y2022\")]\n    advent_2022::SOLVERS,\n];\n\npub fn solvers() {}\n";
+
+    const SOLVER: &str = "/// Collects the solvers.\n#[cfg(any(feature = \"y2021\", feature = \"y2022\"))]\nmacro_rules! solvers {}\n";
+
+    const MANIFEST: &str = "[package]\nname = \"rusty_advent\"\n\n[features]\ndefault = [\"y2021\", \"y2022\"]\ny2021 = []\ny2022 = []\n\n[dependencies]\n";
+
+    /// A throwaway project root holding the template, `Cargo.toml`,
+    /// `advent.rs`, `solver.rs` and a 2022 year with days 1 and 5.
+    fn project(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("rusty_advent_{}_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/advent/advent_2022")).unwrap();
+        fs::write(
+            root.join(".template.rs"),
+            include_str!("../../.template.rs"),
+        )
+        .unwrap();
+        fs::write(root.join("src/advent.rs"), ADVENT).unwrap();
+        fs::write(root.join("src/advent/solver.rs"), SOLVER).unwrap();
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+        fs::write(
+            root.join("src/advent/advent_2022.rs"),
+            "pub mod day_01;\npub mod day_05;\n\nsolvers! {\n    day_01::CalorieCounting,\n    day_05::SupplyStacks,\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/advent/advent_2022/day_05.rs"), "").unwrap();
+        root
+    }
+
+    #[rstest]
+    #[case("Supply Stacks", 5, "SupplyStacks")]
+    #[case("Dive!", 2, "Dive")]
+    #[case("no matter how you slice it", 3, "NoMatterHowYouSliceIt")]
+    #[case("1202 Program Alarm", 2, "Day02")]
+    #[case("", 7, "Day07")]
+    fn test_solver_name(#[case] title: &str, #[case] day: u32, #[case] expected: &str) {
+        assert_eq!(solver_name(title, day), expected);
+    }
+
+    #[rstest]
+    fn test_scaffold_day() {
+        let root = project("scaffold_day");
+        let written = scaffold(
+            &root,
+            &root.join("inputs"),
+            2022,
+            3,
+            "Rucksack \"Reorganization\"",
+        );
+        assert_eq!(written.map(|paths| paths.len()), Ok(3));
+        assert_eq!(
+            fs::read_to_string(root.join("src/advent/advent_2022.rs")).unwrap(),
+            "pub mod day_01;\npub mod day_03;\npub mod day_05;\n\n\
+             solvers! {\n    day_01::CalorieCounting,\n    day_03::RucksackReorganization,\n    day_05::SupplyStacks,\n}\n"
+        );
+        let day = fs::read_to_string(root.join("src/advent/advent_2022/day_03.rs")).unwrap();
+        assert!(day.contains("pub struct RucksackReorganization;"));
+        assert!(day.contains("        2022\n") && day.contains("        3\n"));
+        assert!(day.contains(r#""Rucksack \"Reorganization\"""#));
+        assert!(root.join("inputs/y2022d03.txt").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("src/advent.rs")).unwrap(),
+            ADVENT
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/advent/solver.rs")).unwrap(),
+            SOLVER
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[rstest]
+    #[case(
+        2020,
+        "default = [\"y2020\", \"y2021\", \"y2022\"]\ny2020 = []\ny2021 = []\ny2022 = []",
+        "feature = \"y2020\", feature = \"y2021\", feature = \"y2022\""
+    )]
+    #[case(
+        2023,
+        "default = [\"y2021\", \"y2022\", \"y2023\"]\ny2021 = []\ny2022 = []\ny2023 = []",
+        "feature = \"y2021\", feature = \"y2022\", feature = \"y2023\""
+    )]
+    fn test_scaffold_year(#[case] year: u32, #[case] features: &str, #[case] gate: &str) {
+        let root = project(&format!("scaffold_year_{}", year));
+        let written = scaffold(&root, &root.join("inputs"), year, 1, "Report Repair");
+        assert_eq!(written.map(|paths| paths.len()), Ok(6));
+        assert_eq!(
+            fs::read_to_string(root.join("src/advent/solver.rs")).unwrap(),
+            SOLVER.replace("feature = \"y2021\", feature = \"y2022\"", gate)
+        );
+        assert_eq!(
+            fs::read_to_string(root.join(format!("src/advent/advent_{}.rs", year))).unwrap(),
+            "pub mod day_01;\n\nsolvers! {\n    day_01::ReportRepair,\n}\n"
+        );
+        let advent = fs::read_to_string(root.join("src/advent.rs")).unwrap();
+        let years = [2020, 2021, 2022, 2023]
+            .into_iter()
+            .filter(|y| *y == year || (2021..=2022).contains(y))
+            .collect::<Vec<u32>>();
+        let mods = years
+            .iter()
+            .map(|y| format!("#[cfg(feature = \"y{}\")]\npub mod advent_{};\n", y, y))
+            .collect::<String>();
+        assert!(advent.contains(&mods));
+        let registry = years
+            .iter()
+            .map(|y| {
+                format!(
+                    "    #[cfg(feature = \"y{}\")]\n    advent_{}::SOLVERS,\n",
+                    y, y
+                )
+            })
+            .collect::<String>();
+        assert!(advent.contains(&format!("= &[\n{}];\n", registry)));
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            MANIFEST.replace(
+                "default = [\"y2021\", \"y2022\"]\ny2021 = []\ny2022 = []",
+                features
+            )
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[rstest]
+    #[case(2022, 5)]
+    #[case(2022, 1)]
+    fn test_scaffold_existing_day(#[case] year: u32, #[case] day: u32) {
+        let root = project(&format!("scaffold_existing_{}", day));
+        let before = fs::read_to_string(root.join("src/advent/advent_2022.rs")).unwrap();
+        assert!(matches!(
+            scaffold(&root, &root.join("inputs"), year, day, "Anything"),
+            Err(AdventError::Io { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(root.join("src/advent/advent_2022.rs")).unwrap(),
+            before
+        );
+        assert!(!root.join("inputs").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,56 @@
+use super::{AdventError, Answer, Rng};
+
+/// A solver for a single advent challenge. Each day module implements this
+/// trait and is listed in its year module with the `solvers!` macro, which
+/// is how the runner discovers which challenges exist.
+pub trait Solver: Sync {
+    fn year(&self) -> u32;
+
+    fn day(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    /// Parses the input without solving anything, so that parse time can be
+    /// reported separately. Solvers that don't parse up front return `None`.
+    fn parse(&self, _input: &str) -> Option<Result<(), AdventError>> {
+        None
+    }
+
+    /// Generates a random valid input of roughly `size` lines, boards, moves
+    /// or characters, for benchmarking and fuzzing. Solvers without a
+    /// generator return `None`.
+    fn generate(&self, _size: usize, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+
+    /// Naive reference implementations of both parts, which the solver is
+    /// fuzzed against. Solvers without one return `None`.
+    fn reference(&self) -> Option<Reference> {
+        None
+    }
+
+    fn part_1(&self, input: &str) -> Result<Answer, AdventError>;
+
+    fn part_2(&self, input: &str) -> Result<Answer, AdventError>;
+}
+
+/// Brute-force implementations of both parts of a challenge that are slow
+/// but obviously correct. They return `None` for inputs they can't make
+/// sense of, which the fuzzer then skips instead of counting as a mismatch.
+#[derive(Clone, Copy, Debug)]
+pub struct Reference {
+    pub part_1: fn(&str) -> Option<Answer>,
+    pub part_2: fn(&str) -> Option<Answer>,
+}
+
+/// Collects the solvers of a year's day modules into the year's `SOLVERS`
+/// registry, e.g. `solvers! { day_01::SonarSweep }`. The day modules are
+/// declared next to it as plain `pub mod`s rather than by the macro, because
+/// rustfmt doesn't format modules that are declared inside a macro call.
+/// `cargo run -- new` writes both, so a new day is still a single new module.
+#[cfg(any(feature = "y2021", feature = "y2022"))]
+macro_rules! solvers {
+    ($($module:ident::$solver:ident),* $(,)?) => {
+        pub static SOLVERS: &[&dyn super::Solver] = &[$(&$module::$solver),*];
+    };
+}
@@ -0,0 +1,529 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::fetch::{agent, request_error};
+use super::{select_challenge, AdventError, Answer, Input, Options, Part};
+
+/// How the site judged a submitted answer.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was not judged because the last one was too recent.
+    TooSoon,
+    /// The part was already solved, so the answer was not judged.
+    AlreadySolved,
+    Unknown,
+}
+
+impl Outcome {
+    /// Whether the site actually judged the answer, in which case sending
+    /// it again can only get the same outcome.
+    fn is_judged(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Correct | Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong
+        )
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::TooSoon => "too soon",
+            Outcome::AlreadySolved => "already solved",
+            Outcome::Unknown => "unknown response",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// The site's answer to a submission, with how long it asks to wait before
+/// the next one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Response {
+    pub outcome: Outcome,
+    pub wait: Option<Duration>,
+}
+
+impl Response {
+    /// Reads the outcome out of the page the site answers a submission with.
+    pub fn parse(page: &str) -> Response {
+        let outcome = if page.contains("That's the right answer") {
+            Outcome::Correct
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if page.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Outcome::TooSoon
+        } else if page.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        };
+        Response {
+            outcome,
+            wait: parse_wait(page),
+        }
+    }
+}
+
+/// Finds a wait such as "please wait 5 minutes before trying again" or
+/// "You have 1m 30s left to wait".
+fn parse_wait(page: &str) -> Option<Duration> {
+    if let Some((before, _)) = page.split_once(" left to wait") {
+        let seconds = before
+            .rsplit("You have ")
+            .next()?
+            .split(' ')
+            .map(
+                |token| match token.split_at(token.len().saturating_sub(1)) {
+                    (n, "m") => n.parse::<u64>().ok().map(|n| n * 60),
+                    (n, "s") => n.parse::<u64>().ok(),
+                    _ => None,
+                },
+            )
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(seconds));
+    }
+    let (_, after) = page.split_once("please wait ")?;
+    let minutes = match after.split(' ').next()? {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+/// One submitted answer, with unix timestamps of when it was sent and when
+/// the site allows the next submission.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Attempt {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub at: u64,
+    pub retry_at: Option<u64>,
+}
+
+/// Every answer submitted so far, stored as TOML with one `[[attempt]]`
+/// table each so that no answer is ever sent twice.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Attempts {
+    #[serde(default, rename = "attempt")]
+    attempts: Vec<Attempt>,
+}
+
+impl Attempts {
+    /// Loads the attempts at `path`, where a missing file has no attempts.
+    pub fn load(path: &Path) -> Result<Attempts, AdventError> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str::<Attempts>(&text).map_err(|err| {
+                AdventError::parse_at(
+                    &text,
+                    err.span().map_or(0, |span| span.start),
+                    err.message(),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Attempts::default()),
+            Err(err) => Err(AdventError::Io {
+                path: path.display().to_string(),
+                reason: err.to_string(),
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AdventError> {
+        let text = toml::to_string(self).map_err(|err| AdventError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+        fs::write(path, text).map_err(|err| AdventError::Io {
+            path: path.display().to_string(),
+            reason: err.to_string(),
+        })
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Why `answer` must not be submitted at `now`, if it mustn't: the part
+    /// is already solved, the same answer was already judged, a previous
+    /// answer proves it wrong, or the site asked to wait.
+    pub fn refusal(
+        &self,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &Answer,
+        now: u64,
+    ) -> Option<String> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.year == year && attempt.day == day)
+            .collect::<Vec<&Attempt>>();
+        let of_part = || attempts.iter().filter(|attempt| attempt.part == part);
+        if let Some(solved) = of_part()
+            .find(|attempt| matches!(attempt.outcome, Outcome::Correct | Outcome::AlreadySolved))
+        {
+            return Some(format!(
+                "part {} is already solved with {}",
+                part, solved.answer
+            ));
+        }
+        if let Some(same) = of_part().find(|attempt| {
+            Answer::parse(&attempt.answer) == *answer && attempt.outcome.is_judged()
+        }) {
+            return Some(format!(
+                "{} was already submitted and was {}",
+                answer, same.outcome
+            ));
+        }
+        for attempt in of_part() {
+            let bound = Answer::parse(&attempt.answer);
+            match attempt.outcome {
+                Outcome::TooHigh if *answer >= bound => {
+                    return Some(format!("{} is too high, since {} was", answer, bound));
+                }
+                Outcome::TooLow if *answer <= bound => {
+                    return Some(format!("{} is too low, since {} was", answer, bound));
+                }
+                _ => (),
+            }
+        }
+        let retry_at = attempts
+            .iter()
+            .filter_map(|attempt| attempt.retry_at)
+            .max()
+            .filter(|retry_at| *retry_at > now)?;
+        Some(format!(
+            "the site asked to wait another {}s",
+            retry_at - now
+        ))
+    }
+}
+
+/// Posts answers to the site.
+pub struct Submitter {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Submitter {
+    pub fn new(base_url: &str, session: Option<String>) -> Submitter {
+        Submitter {
+            agent: agent(),
+            base_url: String::from(base_url.trim_end_matches('/')),
+            session,
+        }
+    }
+
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &str,
+    ) -> Result<Response, AdventError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let session = self.session.as_deref().ok_or_else(|| AdventError::Io {
+            path: url.clone(),
+            reason: String::from("no session token to submit with"),
+        })?;
+        let page = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| request_error(&url, err))?
+            .into_string()
+            .map_err(|err| AdventError::Io {
+                path: url.clone(),
+                reason: err.to_string(),
+            })?;
+        Ok(Response::parse(&page))
+    }
+}
+
+/// What became of an answer handed to `submit_answer`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Submission {
+    /// An earlier attempt rules the answer out, so it was not sent.
+    Refused { answer: Answer, reason: String },
+    /// The answer was sent and judged. `saved` is whether the attempt could
+    /// be added to the attempts file afterwards.
+    Sent {
+        answer: Answer,
+        response: Response,
+        saved: Result<(), AdventError>,
+    },
+}
+
+impl Submission {
+    pub fn is_correct(&self) -> bool {
+        matches!(
+            self,
+            Submission::Sent { response, .. } if response.outcome == Outcome::Correct
+        )
+    }
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Submission::Refused { answer, reason } => {
+                write!(f, "not submitting {}: {}", answer, reason)
+            }
+            Submission::Sent {
+                answer, response, ..
+            } => {
+                write!(f, "{}: {}", answer, response.outcome)?;
+                match response.wait {
+                    Some(wait) => write!(f, " (wait {:?} before the next answer)", wait),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Solves one part on the default input in `inputs_dir` and submits its
+/// answer with `submit_answer`.
+pub fn submit_part(
+    submitter: &Submitter,
+    attempts_path: &Path,
+    inputs_dir: &Path,
+    year: u32,
+    day: u32,
+    part: u8,
+) -> Result<Submission, AdventError> {
+    let options = Options {
+        input: Input::Dir(inputs_dir.to_path_buf()),
+        part: if part == 1 { Part::One } else { Part::Two },
+        ..Options::default()
+    };
+    let solution = select_challenge(year, day, &options).ok_or_else(|| {
+        AdventError::NoSolution(format!("there is no solver for {} day {}", year, day))
+    })?;
+    let answer = solution
+        .answer(part)
+        .cloned()
+        .unwrap_or_else(|| Err(AdventError::no_solution("the part was not solved")))?;
+    submit_answer(submitter, attempts_path, year, day, part, &answer, now())
+}
+
+/// Submits an answer unless an earlier attempt in the attempts file rules it
+/// out, and records the attempt there once the site has judged it.
+pub fn submit_answer(
+    submitter: &Submitter,
+    attempts_path: &Path,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: &Answer,
+    now: u64,
+) -> Result<Submission, AdventError> {
+    let mut attempts = Attempts::load(attempts_path)?;
+    if let Some(reason) = attempts.refusal(year, day, part, answer, now) {
+        return Ok(Submission::Refused {
+            answer: answer.clone(),
+            reason,
+        });
+    }
+    let response = submitter.submit(year, day, part, &answer.to_string())?;
+    attempts.record(Attempt {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        outcome: response.outcome,
+        at: now,
+        retry_at: response.wait.map(|wait| now + wait.as_secs()),
+    });
+    Ok(Submission::Sent {
+        answer: answer.clone(),
+        response,
+        saved: attempts.save(attempts_path),
+    })
+}
+
+/// The current unix time in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::stub;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    fn attempt(part: u8, answer: &str, outcome: Outcome, retry_at: Option<u64>) -> Attempt {
+        Attempt {
+            year: 2022,
+            day: 5,
+            part,
+            answer: String::from(answer),
+            outcome,
+            at: 100,
+            retry_at,
+        }
+    }
+
+    #[rstest]
+    #[case(
+        "<p>That's the right answer! You are one gold star closer.</p>",
+        Outcome::Correct,
+        None
+    )]
+    #[case("<p>That's not the right answer; your answer is too high.  If you're stuck, ... please wait one minute before trying again.</p>", Outcome::TooHigh, Some(60))]
+    #[case("<p>That's not the right answer; your answer is too low.  Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.</p>", Outcome::TooLow, Some(300))]
+    #[case("<p>That's not the right answer.  If you're stuck, please wait one minute before trying again.</p>", Outcome::Wrong, Some(60))]
+    #[case("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p>", Outcome::TooSoon, Some(65))]
+    #[case(
+        "<p>You gave an answer too recently. You have 42s left to wait.</p>",
+        Outcome::TooSoon,
+        Some(42)
+    )]
+    #[case(
+        "<p>You don't seem to be solving the right level.  Did you already complete it?</p>",
+        Outcome::AlreadySolved,
+        None
+    )]
+    #[case("<p>Puzzle inputs differ by user.</p>", Outcome::Unknown, None)]
+    fn test_parse_response(
+        #[case] page: &str,
+        #[case] outcome: Outcome,
+        #[case] wait: Option<u64>,
+    ) {
+        assert_eq!(
+            Response::parse(page),
+            Response {
+                outcome,
+                wait: wait.map(Duration::from_secs)
+            }
+        );
+    }
+
+    #[rstest]
+    #[case(1, "CMZ", 200, Some("part 1 is already solved with CMZ"))]
+    #[case(2, "50", 200, Some("50 was already submitted and was too low"))]
+    #[case(2, "120", 200, Some("120 is too high, since 100 was"))]
+    #[case(2, "40", 200, Some("40 is too low, since 50 was"))]
+    #[case(2, "MCD", 200, Some("the site asked to wait another 50s"))]
+    #[case(2, "75", 300, None)]
+    #[case(2, "ABC", 300, None)]
+    fn test_refusal(
+        #[case] part: u8,
+        #[case] answer: &str,
+        #[case] now: u64,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt(1, "CMZ", Outcome::Correct, None));
+        attempts.record(attempt(2, "50", Outcome::TooLow, Some(160)));
+        attempts.record(attempt(2, "100", Outcome::TooHigh, Some(220)));
+        attempts.record(attempt(2, "75", Outcome::TooSoon, Some(250)));
+        assert_eq!(
+            attempts
+                .refusal(2022, 5, part, &Answer::parse(answer), now)
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_round_trip() {
+        let mut attempts = Attempts::default();
+        attempts.record(attempt(1, "CMZ", Outcome::TooHigh, Some(160)));
+        attempts.record(attempt(2, "MCD", Outcome::Correct, None));
+        let text = toml::to_string(&attempts).unwrap();
+        assert!(text.starts_with("[[attempt]]\n"));
+        assert_eq!(toml::from_str::<Attempts>(&text), Ok(attempts));
+    }
+
+    #[rstest]
+    fn test_submit() {
+        let (url, server) = stub::serve(vec![(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        )]);
+        let submitter = Submitter::new(&url, Some(String::from("abc")));
+        assert_eq!(
+            submitter.submit(2022, 5, 2, "MCD"),
+            Ok(Response {
+                outcome: Outcome::Correct,
+                wait: None
+            })
+        );
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/5/answer "));
+        assert!(requests[0].contains("session=abc"));
+        assert!(requests[0].ends_with("level=2&answer=MCD"));
+    }
+
+    #[rstest]
+    fn test_submit_answer() {
+        let path = env::temp_dir().join(format!("rusty_advent_attempts_{}.toml", process::id()));
+        let (url, server) = stub::serve(vec![(
+            200,
+            "<p>That's not the right answer; your answer is too low.  please wait one minute before trying again.</p>",
+        )]);
+        let submitter = Submitter::new(&url, Some(String::from("abc")));
+        let submission = submit_answer(&submitter, &path, 2022, 5, 1, &Answer::from(50), 100);
+        assert_eq!(
+            submission,
+            Ok(Submission::Sent {
+                answer: Answer::from(50),
+                response: Response {
+                    outcome: Outcome::TooLow,
+                    wait: Some(Duration::from_secs(60))
+                },
+                saved: Ok(())
+            })
+        );
+        server.join().unwrap();
+        assert_eq!(
+            submission.unwrap().to_string(),
+            "50: too low (wait 60s before the next answer)"
+        );
+
+        let submission = submit_answer(&submitter, &path, 2022, 5, 1, &Answer::from(40), 300);
+        assert_eq!(
+            submission,
+            Ok(Submission::Refused {
+                answer: Answer::from(40),
+                reason: String::from("40 is too low, since 50 was")
+            })
+        );
+        assert!(!submission.unwrap().is_correct());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,162 @@
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use super::Solution;
+
+/// Wall-clock time spent on each stage of a challenge. A stage is `None`
+/// when it was not run, e.g. an unselected part or a solver without a
+/// separate parse step. Part timings include any parsing the part does.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timing {
+    pub parse: Option<Duration>,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Timing {
+    /// The time spent solving, which excludes the separate parse step.
+    pub fn total(&self) -> Duration {
+        self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+/// Runs `f`, returning its result along with how long it took.
+pub fn time<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Renders a table of challenge timings, slowest first, followed by the
+/// total for each year and, when several years ran, for the whole run.
+pub fn timing_report(solutions: &[Solution]) -> String {
+    let mut sorted = solutions.iter().collect::<Vec<&Solution>>();
+    sorted.sort_by_key(|solution| Reverse(solution.timing.total()));
+    let mut report = format!(
+        "{:<6}{:>4}  {:<26}{:>12}{:>12}{:>12}{:>12}\n",
+        "year", "day", "title", "parse", "part 1", "part 2", "total"
+    );
+    sorted.iter().for_each(|solution| {
+        let _ = writeln!(
+            report,
+            "{:<6}{:>4}  {:<26}{:>12}{:>12}{:>12}{:>12}",
+            solution.year,
+            solution.day,
+            solution.title,
+            fmt_duration(solution.timing.parse),
+            fmt_duration(solution.timing.part1),
+            fmt_duration(solution.timing.part2),
+            fmt_duration(Some(solution.timing.total())),
+        );
+    });
+    let mut years = solutions
+        .iter()
+        .map(|solution| solution.year)
+        .collect::<Vec<u32>>();
+    years.dedup();
+    years.iter().for_each(|year| {
+        let total = solutions
+            .iter()
+            .filter(|solution| solution.year == *year)
+            .map(|solution| solution.timing.total())
+            .sum::<Duration>();
+        let _ = writeln!(
+            report,
+            "{:<74}{:>12}",
+            format!("{} total", year),
+            fmt_duration(Some(total))
+        );
+    });
+    if years.len() > 1 {
+        let total = solutions
+            .iter()
+            .map(|solution| solution.timing.total())
+            .sum::<Duration>();
+        let _ = writeln!(report, "{:<74}{:>12}", "total", fmt_duration(Some(total)));
+    }
+    report
+}
+
+/// Summarizes a parallel run: its wall-clock time, the total time spent in
+/// solvers, and the critical path, i.e. the slowest single challenge which
+/// bounds how fast the run can get with more jobs.
+pub fn parallel_summary(solutions: &[Solution], elapsed: Duration, jobs: usize) -> String {
+    let total = solutions
+        .iter()
+        .map(|solution| solution.timing.total())
+        .sum::<Duration>();
+    let critical_path = solutions
+        .iter()
+        .map(|solution| solution.timing.total())
+        .max()
+        .unwrap_or_default();
+    format!(
+        "Solved {} challenges on {} jobs in {} (total {}, critical path {})",
+        solutions.len(),
+        jobs,
+        fmt_duration(Some(elapsed)),
+        fmt_duration(Some(total)),
+        fmt_duration(Some(critical_path)),
+    )
+}
+
+/// Parses a duration such as `500ms`, `1.5s` or `2m`, where a bare number
+/// is in seconds.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let split = text
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(text.len());
+    let (value, unit) = text.split_at(split);
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("{:?} should start with a number", text))?;
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        unit => return Err(format!("unknown unit {:?}, use ms, s or m", unit)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string())
+}
+
+pub fn fmt_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.2?}", duration),
+        None => String::from("-"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Some(Duration::from_millis(3)), Some(Duration::from_millis(4)), 7)]
+    #[case(Some(Duration::from_millis(3)), None, 3)]
+    #[case(None, None, 0)]
+    fn test_total(
+        #[case] part1: Option<Duration>,
+        #[case] part2: Option<Duration>,
+        #[case] expected: u64,
+    ) {
+        let timing = Timing {
+            parse: Some(Duration::from_millis(1)),
+            part1,
+            part2,
+        };
+        assert_eq!(timing.total(), Duration::from_millis(expected));
+    }
+
+    #[rstest]
+    #[case("500ms", Ok(Duration::from_millis(500)))]
+    #[case("1.5s", Ok(Duration::from_millis(1500)))]
+    #[case("10", Ok(Duration::from_secs(10)))]
+    #[case("2m", Ok(Duration::from_secs(120)))]
+    #[case("2h", Err(String::from("unknown unit \"h\", use ms, s or m")))]
+    #[case("s", Err(String::from("\"s\" should start with a number")))]
+    fn test_parse_duration(#[case] text: &str, #[case] expected: Result<Duration, String>) {
+        assert_eq!(parse_duration(text), expected);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use super::timing::fmt_duration;
+use super::{AdventError, Answer, Solution};
+
+/// Clears the terminal and moves the cursor to the top left.
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Polls files for changes to their modification time. A file that is
+/// created or deleted counts as changed too.
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect::<Vec<(PathBuf, Option<SystemTime>)>>(),
+        }
+    }
+
+    /// Whether any file changed since the last check.
+    pub fn changed(&mut self) -> bool {
+        let mut changed = false;
+        for (path, last) in self.files.iter_mut() {
+            let modified = modified(path);
+            changed |= *last != modified;
+            *last = modified;
+        }
+        changed
+    }
+
+    /// Blocks until a file changes, checking every `interval`.
+    pub fn wait(&mut self, interval: Duration) {
+        while !self.changed() {
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Shows the parts a solution ran with their timings, marking each answer
+/// that differs from the previous run's.
+pub fn watch_report(label: &str, current: &Solution, previous: Option<&Solution>) -> String {
+    let mut report = format!("{}\n", label);
+    [
+        (1, &current.part1, current.timing.part1),
+        (2, &current.part2, current.timing.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, answer, duration)| Some((part, answer.as_ref()?, duration)))
+    .for_each(|(part, answer, duration)| {
+        let before = previous.and_then(|previous| previous.answer(part));
+        let change = match (before, answer) {
+            (None, _) => String::new(),
+            (Some(before), answer) if before == answer => String::from("  (unchanged)"),
+            (Some(before), _) => format!("  (was {})", fmt_answer(before)),
+        };
+        let _ = writeln!(
+            report,
+            "  part {}: {:<24}{:>12}{}",
+            part,
+            fmt_answer(answer),
+            fmt_duration(duration),
+            change
+        );
+    });
+    report
+}
+
+fn fmt_answer(answer: &Result<Answer, AdventError>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!("FAILED: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advent::Timing;
+    use rstest::rstest;
+    use std::env;
+    use std::process;
+
+    fn solution(part1: &str, part2: Result<Answer, AdventError>) -> Solution {
+        Solution {
+            year: 2022,
+            day: 5,
+            title: "Supply Stacks",
+            part1: Some(Ok(Answer::from(part1))),
+            part2: Some(part2),
+            timing: Timing {
+                parse: None,
+                part1: Some(Duration::from_micros(1500)),
+                part2: Some(Duration::from_micros(2500)),
+            },
+        }
+    }
+
+    #[rstest]
+    #[case(
+        None,
+        "example\n  part 1: CMZ                           1.50ms\n  part 2: MCD                           2.50ms\n"
+    )]
+    #[case(
+        Some(solution("CMZ", Ok(Answer::from("ABC")))),
+        "example\n  part 1: CMZ                           1.50ms  (unchanged)\n  part 2: MCD                           2.50ms  (was ABC)\n"
+    )]
+    #[case(
+        Some(solution("CMZ", Err(AdventError::no_solution("stack 4 is empty")))),
+        "example\n  part 1: CMZ                           1.50ms  (unchanged)\n  part 2: MCD                           2.50ms  (was FAILED: no solution: stack 4 is empty)\n"
+    )]
+    fn test_watch_report(#[case] previous: Option<Solution>, #[case] expected: &str) {
+        let current = solution("CMZ", Ok(Answer::from("MCD")));
+        assert_eq!(
+            watch_report("example", &current, previous.as_ref()),
+            expected
+        );
+    }
+
+    #[rstest]
+    fn test_changed() {
+        let path = env::temp_dir().join(format!("rusty_advent_watch_{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(!watcher.changed());
+        fs::write(&path, "1").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+}
@@ -0,0 +1,5 @@
+//! Solvers for Advent of Code challenges, along with the runner that selects
+//! and solves them, checks their answers and talks to the site. The
+//! `rusty_advent` binary is a command line interface on top of this library.
+
+pub mod advent;
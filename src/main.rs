@@ -1,10 +1,25 @@
-use clap::Parser;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant};
 
-mod advent;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+
+use rusty_advent::advent::{
+    self, AdventError, Answers, Baseline, Config, Coverage, Fetched, Fetcher, Format, History,
+    Input, Options, Part, Run, Solution, Submission, Submitter, Tally, Watcher,
+};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Advent Year
     #[arg(short, long, default_value_t = 0)]
     year: u32,
@@ -12,24 +27,799 @@ struct Args {
     /// Advent Day
     #[arg(short, long, default_value_t = 0)]
     day: u32,
+
+    /// Which part to solve: 1, 2 or both
+    #[arg(short, long, default_value = "both")]
+    part: Part,
+
+    /// Print a timing report after the solutions
+    #[arg(short, long)]
+    time: bool,
+
+    /// Output format: text, json, csv or tsv
+    #[arg(short, long, default_value = "text")]
+    format: Format,
+
+    /// Input file for a single challenge, or `-` to read from stdin
+    #[arg(short, long)]
+    input: Option<String>,
+
+    /// Read the named input `yYYYYdDD/<NAME>.txt` from the inputs directory
+    #[arg(short = 'n', long, conflicts_with = "input")]
+    input_name: Option<String>,
+
+    /// Solve on every input of each challenge and tabulate the results
+    #[arg(long, conflicts_with_all = ["input", "input_name", "watch", "verify", "record"])]
+    all_inputs: bool,
+
+    /// How many challenges to solve at once, 0 for one per core
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Give up on a challenge that takes longer than this, e.g. 10s or 500ms
+    #[arg(long, value_parser = advent::parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Check the answers against the answers file instead of printing them
+    #[arg(long, conflicts_with = "record")]
+    verify: bool,
+
+    /// Save the answers to the answers file instead of printing them
+    #[arg(long)]
+    record: bool,
+
+    /// Answers file used by --verify and --record
+    #[arg(long, default_value = "answers.toml", global = true)]
+    answers: PathBuf,
+
+    /// File every run's answers and timings are appended to
+    #[arg(long, default_value = "history.toml", global = true)]
+    history: PathBuf,
+
+    /// Don't append this run to the history
+    #[arg(long)]
+    no_history: bool,
+
+    /// Re-run a single challenge whenever its input, or the --input file, changes
+    #[arg(short, long, conflicts_with_all = ["verify", "record"])]
+    watch: bool,
+
+    /// Directory holding the `yYYYYdDD.txt` inputs
+    #[arg(
+        long,
+        env = "RUSTY_ADVENT_INPUTS",
+        default_value = "inputs",
+        global = true
+    )]
+    inputs_dir: PathBuf,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Scaffold a new day from `.template.rs` and register it
+    New {
+        /// Advent Year
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(2015..))]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Challenge title, also used to name the solver
+        #[arg(short, long, default_value = "")]
+        title: String,
+
+        /// Root of the rusty_advent checkout to scaffold into
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Extract the examples and their answers from a saved puzzle page
+    Examples {
+        /// Advent Year
+        #[arg(short, long)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long)]
+        day: u32,
+
+        /// The puzzle page saved as HTML
+        #[arg(long)]
+        page: PathBuf,
+
+        /// Directory the examples are written to, under `yYYYYdDD`
+        #[arg(long, default_value = "examples")]
+        out: PathBuf,
+    },
+    /// Show which days have solvers, inputs, accepted answers and tests
+    List {
+        /// Root of the rusty_advent checkout, where the day sources are read
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+    /// Show the timings of past runs and flag the ones that got slower
+    History {
+        /// Advent Year
+        #[arg(short, long, default_value_t = 0)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long, default_value_t = 0)]
+        day: u32,
+
+        /// Percent slower than the best earlier run at which a run is flagged
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// How many of the latest runs to show per part
+        #[arg(long, default_value_t = 10)]
+        last: usize,
+    },
+    /// Time each solver over many runs and report statistics per part
+    Bench {
+        /// Advent Year
+        #[arg(short, long, default_value_t = 0)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long, default_value_t = 0)]
+        day: u32,
+
+        /// Which part to benchmark: 1, 2 or both
+        #[arg(short, long, default_value = "both")]
+        part: Part,
+
+        /// Untimed runs before timing starts
+        #[arg(long, default_value_t = 3)]
+        warmup: usize,
+
+        /// Timed runs per part
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+
+        /// Baseline file the medians are compared against
+        #[arg(long, default_value = "bench.toml")]
+        baseline: PathBuf,
+
+        /// Save the results as the new baseline instead of comparing
+        #[arg(long)]
+        save: bool,
+    },
+    /// Print a random input in the puzzle's format, the same for the same seed
+    Generate {
+        /// Advent Year
+        #[arg(short, long)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long)]
+        day: u32,
+
+        /// How many lines, boards, moves or characters to generate
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+
+        /// Seed of the random number generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Check solvers against their naive references on generated inputs
+    Fuzz {
+        /// Advent Year
+        #[arg(short, long, default_value_t = 0)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long, default_value_t = 0)]
+        day: u32,
+
+        /// How many inputs to generate per challenge
+        #[arg(long, default_value_t = advent::FUZZ_CASES)]
+        cases: usize,
+
+        /// Size of the largest input, which the inputs grow to from 1
+        #[arg(long, default_value_t = advent::FUZZ_SIZE)]
+        size: usize,
+
+        /// Seed of the first input, incremented for each one after it
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Directory the shrunk inputs of mismatches are written to
+        #[arg(long, default_value = "mismatches")]
+        out: PathBuf,
+    },
+    /// Download puzzle inputs that aren't in the inputs directory yet
+    Fetch {
+        /// Advent Year
+        #[arg(short, long, default_value_t = 0)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long, default_value_t = 0)]
+        day: u32,
+
+        #[command(flatten)]
+        remote: Remote,
+
+        /// Seconds to wait between requests
+        #[arg(long, default_value_t = 3)]
+        interval: u64,
+    },
+    /// Solve one part of a challenge and submit its answer
+    Submit {
+        /// Advent Year
+        #[arg(short, long)]
+        year: u32,
+
+        /// Advent Day
+        #[arg(short, long)]
+        day: u32,
+
+        /// Which part to submit: 1 or 2
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        #[command(flatten)]
+        remote: Remote,
+
+        /// File recording every submitted answer
+        #[arg(long, default_value = "attempts.toml")]
+        attempts: PathBuf,
+    },
+}
+
+/// How to reach the advent of code site.
+#[derive(Debug, clap::Args)]
+struct Remote {
+    /// Base URL of the advent of code site
+    #[arg(long, env = "RUSTY_ADVENT_URL", default_value = advent::BASE_URL)]
+    base_url: String,
+
+    /// Session token, the `session` cookie of a logged in browser
+    #[arg(long, env = "RUSTY_ADVENT_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// File holding the session token, used when --session isn't given
+    #[arg(long, default_value = ".session")]
+    session_file: PathBuf,
+}
+
+impl Remote {
+    fn session(&self) -> Option<String> {
+        self.session
+            .clone()
+            .or_else(|| fs::read_to_string(&self.session_file).ok())
+            .map(|session| session.trim().trim_start_matches("session=").to_string())
+            .filter(|session| !session.is_empty())
+    }
 }
 
 fn main() {
-    let solutions = match Args::parse() {
-        Args { year: 0, day: 0 } => advent::select_all_challenges(),
-        Args { year, day: 0 } => advent::select_challenges_from_year(year),
-        Args { year: 0, day } => advent::select_challenges_with_day(day),
-        Args { year, day } => match advent::select_challenge(year, day) {
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let config = env::current_dir()
+        .map_err(|err| AdventError::Io {
+            path: String::from("."),
+            reason: err.to_string(),
+        })
+        .and_then(|dir| Config::discover(&dir));
+    match config {
+        Ok(Some(config)) => apply_config(&mut args, &matches, config),
+        Ok(None) => (),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+    if let Some(command) = &args.command {
+        process::exit(run_command(command, &args));
+    }
+    if args.watch {
+        if args.year == 0 || args.day == 0 {
+            Args::command()
+                .error(
+                    ErrorKind::MissingRequiredArgument,
+                    "--watch requires both --year and --day",
+                )
+                .exit();
+        }
+        watch(&args);
+    }
+    let input = match &args.input {
+        Some(_) if args.year == 0 || args.day == 0 => Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "--input requires both --year and --day",
+            )
+            .exit(),
+        Some(arg) => Input::from_arg(arg),
+        None => dir_input(&args),
+    };
+    let streaming = args.format == Format::Text && !args.verify && !args.record;
+    let options = Options {
+        input,
+        part: args.part,
+        time: args.time,
+        jobs: args.jobs,
+        timeout: args.timeout,
+        on_solution: streaming.then_some(print_solution as fn(&Solution)),
+    };
+    if args.all_inputs {
+        let runs = advent::select_challenges_on_all_inputs(
+            args.year,
+            args.day,
+            &args.inputs_dir,
+            &options,
+        );
+        if runs.is_empty() {
+            println!("Sorry! No solutions...");
+        } else {
+            print!("{}", advent::inputs_report(&runs));
+        }
+        if runs.iter().any(|(_, solution)| !solution.is_ok()) {
+            process::exit(1);
+        }
+        return;
+    }
+    let start = Instant::now();
+    let solutions = match &args {
+        Args {
+            year: 0, day: 0, ..
+        } => advent::select_all_challenges(&options),
+        Args { year, day: 0, .. } => advent::select_challenges_from_year(*year, &options),
+        Args { year: 0, day, .. } => advent::select_challenges_with_day(*day, &options),
+        Args { year, day, .. } => match advent::select_challenge(*year, *day, &options) {
             Some(solution) => vec![solution],
             None => Vec::new(),
         },
     };
-    if solutions.is_empty() {
+    let elapsed = start.elapsed();
+    if let (false, Input::Dir(_)) = (args.no_history, &options.input) {
+        // Parts timed side by side on several threads would skew the history.
+        if options.jobs() > 1 {
+            eprintln!(
+                "note: the run was not added to the history, since it ran on {} jobs",
+                options.jobs()
+            );
+        } else {
+            let runs = Run::from_solutions(
+                &solutions,
+                advent::git_commit(Path::new(".")).as_deref(),
+                advent::now(),
+            );
+            if let Err(err) = History::append(&args.history, runs) {
+                eprintln!("warning: the run was not added to the history: {}", err);
+            }
+        }
+    }
+    if args.verify {
+        process::exit(verify(&solutions, &args.answers));
+    }
+    if args.record {
+        process::exit(record(&solutions, &args.answers));
+    }
+    if args.format != Format::Text {
+        print!("{}", advent::render(&solutions, args.format));
+    } else if solutions.is_empty() {
         println!("Sorry! No solutions...");
     } else {
-        solutions
-            .iter()
-            .rev()
-            .for_each(|solution| println!("{}", solution));
+        if args.time {
+            print!("\n{}", advent::timing_report(&solutions));
+        }
+        if options.jobs() > 1 {
+            println!(
+                "\n{}",
+                advent::parallel_summary(&solutions, elapsed, options.jobs())
+            );
+        }
+    }
+    if solutions.iter().any(|solution| !solution.is_ok()) {
+        process::exit(1);
+    }
+}
+
+/// Takes each setting the project config has from it, unless the setting
+/// was given on the command line or in the environment.
+fn apply_config(args: &mut Args, matches: &ArgMatches, config: Config) {
+    let defaulted = |matches: &ArgMatches, id: &str| {
+        matches.value_source(id) == Some(ValueSource::DefaultValue)
+    };
+    if let (Some(inputs_dir), true) = (config.inputs_dir, defaulted(matches, "inputs_dir")) {
+        args.inputs_dir = inputs_dir;
+    }
+    if let (Some(answers), true) = (config.answers, defaulted(matches, "answers")) {
+        args.answers = answers;
+    }
+    if let (Some(history), true) = (config.history, defaulted(matches, "history")) {
+        args.history = history;
+    }
+    if let (Some(year), true) = (config.year, defaulted(matches, "year")) {
+        args.year = year;
+    }
+    if let (Some(format), true) = (config.format, defaulted(matches, "format")) {
+        args.format = format;
+    }
+    if let (Some(jobs), true) = (config.jobs, defaulted(matches, "jobs")) {
+        args.jobs = jobs;
+    }
+    let Some((_, matches)) = matches.subcommand() else {
+        return;
+    };
+    match &mut args.command {
+        Some(Command::Fetch { year, remote, .. }) => {
+            if let (Some(config_year), true) = (config.year, defaulted(matches, "year")) {
+                *year = config_year;
+            }
+            if let (Some(session_file), true) =
+                (config.session_file, defaulted(matches, "session_file"))
+            {
+                remote.session_file = session_file;
+            }
+        }
+        Some(Command::Submit { remote, .. }) => {
+            if let (Some(session_file), true) =
+                (config.session_file, defaulted(matches, "session_file"))
+            {
+                remote.session_file = session_file;
+            }
+        }
+        _ => (),
+    }
+}
+
+/// The input read from the inputs directory: the named one if given.
+fn dir_input(args: &Args) -> Input {
+    match &args.input_name {
+        Some(name) => Input::Named {
+            dir: args.inputs_dir.clone(),
+            name: name.clone(),
+        },
+        None => Input::Dir(args.inputs_dir.clone()),
+    }
+}
+
+/// Solves one challenge on its input and on the --input file, if given,
+/// again and again whenever one of them changes.
+fn watch(args: &Args) -> ! {
+    let Some(solver) = advent::solvers()
+        .into_iter()
+        .find(|solver| solver.year() == args.year && solver.day() == args.day)
+    else {
+        eprintln!(
+            "error: there is no solver for {} day {}",
+            args.year, args.day
+        );
+        process::exit(1);
+    };
+    let mut inputs = dir_input(args)
+        .path(args.year, args.day)
+        .into_iter()
+        .collect::<Vec<PathBuf>>();
+    match args.input.as_deref() {
+        Some("-") => Args::command()
+            .error(ErrorKind::ArgumentConflict, "--watch can't watch stdin")
+            .exit(),
+        Some(path) => inputs.push(PathBuf::from(path)),
+        None => (),
+    }
+    let mut watcher = Watcher::new(inputs.clone());
+    let mut previous = inputs
+        .iter()
+        .map(|_| None)
+        .collect::<Vec<Option<Solution>>>();
+    loop {
+        print!("{}", advent::CLEAR_SCREEN);
+        println!("{} day {}: {}\n", args.year, args.day, solver.title());
+        for (path, previous) in inputs.iter().zip(previous.iter_mut()) {
+            let options = Options {
+                input: Input::File(path.clone()),
+                part: args.part,
+                timeout: args.timeout,
+                ..Options::default()
+            };
+            if let Some(solution) = advent::select_challenge(args.year, args.day, &options) {
+                let label = path.display().to_string();
+                println!(
+                    "{}",
+                    advent::watch_report(&label, &solution, previous.as_ref())
+                );
+                *previous = Some(solution);
+            }
+        }
+        println!("watching for changes, press ctrl-c to stop");
+        watcher.wait(Duration::from_millis(250));
+    }
+}
+
+fn print_solution(solution: &Solution) {
+    println!("{}", solution);
+    solution
+        .errors()
+        .into_iter()
+        .for_each(|err| eprintln!("  error: {}", err));
+}
+
+/// Runs a subcommand, returning the exit code.
+fn run_command(command: &Command, args: &Args) -> i32 {
+    match command {
+        Command::New {
+            year,
+            day,
+            title,
+            root,
+        } => match advent::scaffold(root, &root.join(&args.inputs_dir), *year, *day, title) {
+            Ok(written) => {
+                println!("Scaffolded {} day {}", year, day);
+                written
+                    .iter()
+                    .for_each(|path| println!("  wrote {}", path.display()));
+                0
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                1
+            }
+        },
+        Command::Examples {
+            year,
+            day,
+            page,
+            out,
+        } => {
+            let page = match fs::read_to_string(page) {
+                Ok(page) => page,
+                Err(err) => {
+                    eprintln!("error: could not read {}: {}", page.display(), err);
+                    return 1;
+                }
+            };
+            let examples = advent::extract_examples(&page);
+            if examples.is_empty() {
+                eprintln!("error: the page has no examples");
+                return 1;
+            }
+            let dir = out.join(advent::input_dir_name(*year, *day));
+            match advent::save_examples(&dir, &examples) {
+                Ok(written) => {
+                    written
+                        .iter()
+                        .for_each(|path| println!("wrote {}", path.display()));
+                    0
+                }
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    1
+                }
+            }
+        }
+        Command::History {
+            year,
+            day,
+            threshold,
+            last,
+        } => match History::load(&args.history) {
+            Ok(history) => {
+                print!("{}", history.report(*year, *day, *threshold, *last));
+                0
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                1
+            }
+        },
+        Command::Bench {
+            year,
+            day,
+            part,
+            warmup,
+            runs,
+            baseline,
+            save,
+        } => {
+            let input = Input::Dir(args.inputs_dir.clone());
+            let benches = advent::bench_challenges(*year, *day, &input, *part, *warmup, *runs);
+            if benches.is_empty() {
+                eprintln!("error: no solver for the selected challenges");
+                return 1;
+            }
+            if *save {
+                print!("{}", advent::bench_report(&benches, None));
+                return match Baseline::of(&benches).save(baseline) {
+                    Ok(()) => {
+                        println!("saved baseline to {}", baseline.display());
+                        0
+                    }
+                    Err(err) => {
+                        eprintln!("error: {}", err);
+                        1
+                    }
+                };
+            }
+            match Baseline::load(baseline) {
+                Ok(saved) => {
+                    print!("{}", advent::bench_report(&benches, saved.as_ref()));
+                    0
+                }
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    1
+                }
+            }
+        }
+        Command::Generate {
+            year,
+            day,
+            size,
+            seed,
+        } => match advent::generate(*year, *day, *size, *seed) {
+            Ok(input) => {
+                println!("{}", input);
+                0
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                1
+            }
+        },
+        Command::Fuzz {
+            year,
+            day,
+            cases,
+            size,
+            seed,
+            out,
+        } => {
+            let mut code = 0;
+            advent::solvers()
+                .into_iter()
+                .filter(|solver| {
+                    (*year == 0 || solver.year() == *year) && (*day == 0 || solver.day() == *day)
+                })
+                .for_each(|solver| match advent::fuzz(solver, *cases, *size, *seed) {
+                    Some(Ok(())) => println!(
+                        "{} day {}: {} cases agree",
+                        solver.year(),
+                        solver.day(),
+                        cases
+                    ),
+                    Some(Err(mismatch)) => {
+                        code = 1;
+                        println!("{}", mismatch);
+                        match mismatch.save(out) {
+                            Ok(path) => println!("  wrote {}", path.display()),
+                            Err(err) => eprintln!("error: {}", err),
+                        }
+                    }
+                    None => println!(
+                        "{} day {}: no generator or reference",
+                        solver.year(),
+                        solver.day()
+                    ),
+                });
+            code
+        }
+        Command::List { root } => match Answers::load(&args.answers) {
+            Ok(answers) => {
+                print!("{}", Coverage::collect(root, &args.inputs_dir, &answers));
+                0
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                1
+            }
+        },
+        Command::Fetch {
+            year,
+            day,
+            remote,
+            interval,
+        } => {
+            let challenges = match (year, day) {
+                (0, _) | (_, 0) => advent::solvers()
+                    .into_iter()
+                    .filter(|solver| *year == 0 || solver.year() == *year)
+                    .filter(|solver| *day == 0 || solver.day() == *day)
+                    .map(|solver| (solver.year(), solver.day()))
+                    .collect::<Vec<(u32, u32)>>(),
+                _ => vec![(*year, *day)],
+            };
+            let mut fetcher = Fetcher::new(
+                &remote.base_url,
+                remote.session(),
+                Duration::from_secs(*interval),
+            );
+            let mut code = 0;
+            for (year, day) in challenges {
+                match fetcher.fetch(&args.inputs_dir, year, day) {
+                    Ok(Fetched::Cached(path)) => println!("cached     {}", path.display()),
+                    Ok(Fetched::Downloaded(path)) => println!("downloaded {}", path.display()),
+                    Err(err) => {
+                        eprintln!("error: {}", err);
+                        code = 1;
+                    }
+                }
+            }
+            code
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            remote,
+            attempts,
+        } => submit(*year, *day, *part, remote, attempts, &args.inputs_dir),
+    }
+}
+
+/// Submits the answer of one part unless an earlier attempt rules it out,
+/// returning the exit code.
+fn submit(year: u32, day: u32, part: u8, remote: &Remote, path: &Path, inputs_dir: &Path) -> i32 {
+    let submitter = Submitter::new(&remote.base_url, remote.session());
+    match advent::submit_part(&submitter, path, inputs_dir, year, day, part) {
+        Ok(submission @ Submission::Refused { .. }) => {
+            eprintln!("{}", submission);
+            1
+        }
+        Ok(submission) => {
+            if let Submission::Sent {
+                saved: Err(err), ..
+            } = &submission
+            {
+                eprintln!("error: {}", err);
+            }
+            println!("{}", submission);
+            if submission.is_correct() {
+                0
+            } else {
+                1
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            1
+        }
+    }
+}
+
+/// Prints a verdict for every part that ran, returning the exit code.
+fn verify(solutions: &[Solution], path: &Path) -> i32 {
+    let checks = match advent::verify_answers(path, solutions) {
+        Ok(checks) => checks,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return 1;
+        }
+    };
+    checks.iter().for_each(|check| println!("{}", check));
+    let tally = Tally::of(&checks);
+    println!("\n{}", tally);
+    if tally.failed > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// Saves the solved answers to the answers file, returning the exit code.
+fn record(solutions: &[Solution], path: &Path) -> i32 {
+    if let Err(err) = advent::record_answers(path, solutions) {
+        eprintln!("error: {}", err);
+        return 1;
     }
+    println!(
+        "Recorded {} solutions to {}",
+        solutions.len(),
+        path.display()
+    );
+    let mut code = 0;
+    solutions
+        .iter()
+        .flat_map(|solution| solution.errors())
+        .for_each(|err| {
+            eprintln!("  error: {}", err);
+            code = 1;
+        });
+    code
 }